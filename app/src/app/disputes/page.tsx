@@ -2,7 +2,7 @@
 
 import React from 'react';
 import { ShieldAlert, Clock, CheckCircle, AlertTriangle, Lock, ChevronRight } from 'lucide-react';
-import { useDisputeRecords, useMerchantReputation, useMerchantBond } from '@/lib/hooks';
+import { useDisputeRecords, useMerchantReputation, useMerchantConfig, useMerchantBond } from '@/lib/hooks';
 import { useWallet } from '@/lib/useWallet';
 import { lamportsToSol, shortenAddress } from '@/lib/solana';
 
@@ -10,7 +10,8 @@ export default function DisputesPage() {
     const publicKey = useWallet();
     const disputes = useDisputeRecords(publicKey);
     const rep = useMerchantReputation(publicKey);
-    const bond = useMerchantBond(publicKey);
+    const config = useMerchantConfig(publicKey);
+    const bond = useMerchantBond(config.data?.mint ?? null);
 
     const bondAmount = bond.data ? lamportsToSol(bond.data.bondedLamports) : '—';
 
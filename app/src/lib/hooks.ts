@@ -273,9 +273,9 @@ export function useMerchantReputation(merchant: PublicKey | null): DataState<Mer
     );
 }
 
-export function useMerchantBond(merchant: PublicKey | null): DataState<MerchantBond> {
+export function useMerchantBond(mint: PublicKey | null): DataState<MerchantBond> {
     return useAccountData(
-        () => merchant ? findMerchantBondPda(merchant) : null,
+        () => mint ? findMerchantBondPda(mint) : null,
         decodeMerchantBond
     );
 }
@@ -384,7 +384,7 @@ export function useDisputeRecords(merchant: PublicKey | null): DataState<Dispute
                 // Filter by merchant pubkey at offset 9 (after 8-byte discriminator + 1 bump)
                 const accounts = await conn.getProgramAccounts(PROGRAM_ID, {
                     filters: [
-                        { dataSize: 138 }, // Approximate size of DisputeRecord
+                        { dataSize: 182 }, // DisputeRecord::LEN
                         { memcmp: { offset: 9, bytes: merchant.toBase58() } },
                     ],
                 });
@@ -401,15 +401,24 @@ export function useDisputeRecords(merchant: PublicKey | null): DataState<Dispute
                         1: 'Dismissed' as DisputeStatus,
                         2: 'UpheldByTimeout' as DisputeStatus,
                         3: 'UpheldByVote' as DisputeStatus,
+                        4: 'InArbitration' as DisputeStatus,
+                        5: 'InVote' as DisputeStatus,
+                        6: 'UpheldByArbiter' as DisputeStatus,
                     };
                     const [stakeLamports, o6] = readU64(buf, o); o = o6;
                     const [raisedAt, o7] = readI64(buf, o); o = o7;
                     const [hasResolved, o8] = readBool(buf, o); o = o8;
                     const resolvedAt = hasResolved ? readI64(buf, o)[0] : null;
+                    if (hasResolved) o += 8;
+                    const [hasEvidence, o9] = readBool(buf, o); o = o9;
+                    const evidenceHash = hasEvidence ? new Uint8Array(buf.subarray(o, o + 32)) : null;
+                    if (hasEvidence) o += 32;
+                    const [hasResponded, o10] = readBool(buf, o); o = o10;
+                    const respondedAt = hasResponded ? readI64(buf, o)[0] : null;
                     return {
                         bump, merchant: merchPk, referral, watchdog,
                         status: statusMap[statusByte] || ('Pending' as DisputeStatus),
-                        stakeLamports, raisedAt, resolvedAt,
+                        stakeLamports, raisedAt, resolvedAt, evidenceHash, respondedAt,
                     };
                 });
                 if (mounted) setState({ data: disputes, loading: false, error: null });
@@ -69,11 +69,11 @@ export function findMerchantReputationPda(merchant: PublicKey): [PublicKey, numb
 
 /**
  * Derive MerchantBond PDA.
- * Seeds: "merchant_bond", merchant.key()
+ * Seeds: "merchant_bond", mint.key()
  */
-export function findMerchantBondPda(merchant: PublicKey): [PublicKey, number] {
+export function findMerchantBondPda(mint: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-        [Buffer.from('merchant_bond'), merchant.toBuffer()],
+        [Buffer.from('merchant_bond'), mint.toBuffer()],
         PROGRAM_ID
     );
 }
@@ -102,11 +102,33 @@ export function findCommissionLedgerPda(referrer: PublicKey, merchant: PublicKey
 
 /**
  * Derive DisputeRecord PDA.
- * Seeds: "dispute", merchant.key(), referral.key()
+ * Seeds: "dispute", referral.key(), watchdog.key()
  */
-export function findDisputeRecordPda(merchant: PublicKey, referral: PublicKey): [PublicKey, number] {
+export function findDisputeRecordPda(referral: PublicKey, watchdog: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-        [Buffer.from('dispute'), merchant.toBuffer(), referral.toBuffer()],
+        [Buffer.from('dispute'), referral.toBuffer(), watchdog.toBuffer()],
+        PROGRAM_ID
+    );
+}
+
+/**
+ * Derive the PDA that owns a merchant treasury's token account.
+ * Seeds: "treasury", mint.key()
+ */
+export function findTreasuryAuthorityPda(mint: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('treasury'), mint.toBuffer()],
+        PROGRAM_ID
+    );
+}
+
+/**
+ * Derive the lamport escrow holding a dispute's stake.
+ * Seeds: "dispute_escrow", dispute.key()
+ */
+export function findDisputeEscrowPda(dispute: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('dispute_escrow'), dispute.toBuffer()],
         PROGRAM_ID
     );
 }
@@ -12,6 +12,9 @@ export enum DisputeStatus {
     Dismissed = 'Dismissed',
     UpheldByTimeout = 'UpheldByTimeout',
     UpheldByVote = 'UpheldByVote',
+    InArbitration = 'InArbitration',
+    InVote = 'InVote',
+    UpheldByArbiter = 'UpheldByArbiter',
 }
 
 export enum GenSource {
@@ -96,6 +99,8 @@ export interface DisputeRecord {
     stakeLamports: number;
     raisedAt: number;
     resolvedAt: number | null;
+    evidenceHash: Uint8Array | null;
+    respondedAt: number | null;
 }
 
 export interface CommissionLedger {
@@ -64,4 +64,196 @@ pub enum ViralSyncError {
     
     #[msg("Access Denied or Invalid Authority")]
     AccessDenied,
+    
+    #[msg("Referrer generation does not belong to the referral's referrer and mint")]
+    ReferrerGenerationMismatch,
+    
+    #[msg("Proof-of-Influence inputs must be distinct generations owned by the same wallet")]
+    InvalidPoiGeneration,
+    
+    #[msg("Identity provider is not registered")]
+    UnknownIdentityProvider,
+    
+    #[msg("Missing or invalid Ed25519 identity attestation")]
+    InvalidIdentitySignature,
+    
+    #[msg("Wallet already has an identity bound on this mint")]
+    IdentityAlreadyBound,
+    
+    #[msg("Identity commitment is already used by another wallet on this mint")]
+    IdentityCommitmentInUse,
+    
+    #[msg("No identity bound to this wallet")]
+    IdentityNotBound,
+    
+    #[msg("Session key is revoked or expired")]
+    SessionKeyInvalid,
+    
+    #[msg("Session key is not scoped to this token generation")]
+    SessionGenerationMismatch,
+    
+    #[msg("Session key spend cap exceeded")]
+    SessionSpendCapExceeded,
+    
+    #[msg("Session key lacks the permission for this action")]
+    SessionPermissionDenied,
+    
+    #[msg("Session key daily spend window exceeded")]
+    SessionDailyLimitExceeded,
+    
+    #[msg("Destination is not on the session key's allow-list")]
+    SessionDestinationNotAllowed,
+    
+    #[msg("Too many allowed destinations for a session key")]
+    TooManySessionDestinations,
+    
+    #[msg("Session key is still valid and cannot be closed permissionlessly")]
+    SessionKeyStillActive,
+    
+    #[msg("Escrow needs a recipient, a claim code, or both")]
+    EscrowClaimRuleRequired,
+    
+    #[msg("Escrow expiry must be in the future")]
+    InvalidEscrowExpiry,
+    
+    #[msg("Escrow has expired")]
+    EscrowExpired,
+    
+    #[msg("Claimant is not the escrow recipient and presented no valid claim code")]
+    EscrowClaimNotAuthorized,
+    
+    #[msg("Claim exceeds the escrow's remaining balance")]
+    EscrowInsufficientBalance,
+    
+    #[msg("Escrow has not expired yet")]
+    EscrowNotExpired,
+    
+    #[msg("Invalid link drop: claimer count out of range or no per-claim amount")]
+    InvalidLinkDrop,
+    
+    #[msg("This wallet already claimed from the link drop")]
+    EscrowAlreadyClaimed,
+    
+    #[msg("Link drop has reached its maximum number of claimers")]
+    EscrowFullyClaimed,
+    
+    #[msg("Intermediary generation is not owned by its escrow PDA")]
+    InvalidIntermediary,
+    
+    #[msg("Bond is locked; request an unlock first")]
+    BondLocked,
+    
+    #[msg("Bond unlock timelock has not elapsed")]
+    BondTimelockActive,
+    
+    #[msg("Merchant is closing; only bond-share redemption and burns are allowed")]
+    MerchantSunset,
+    
+    #[msg("Merchant close has not been initiated")]
+    CloseNotInitiated,
+    
+    #[msg("Close window has ended; the close can no longer be cancelled")]
+    CloseWindowEnded,
+    
+    #[msg("Bond shares were already redeemed during the close window")]
+    BondSharesRedeemed,
+    
+    #[msg("Dispute is not awaiting a merchant response")]
+    DisputeNotPending,
+    
+    #[msg("Merchant response window for this dispute has closed")]
+    DisputeResponseWindowClosed,
+    
+    #[msg("Dispute is not in arbitration")]
+    DisputeNotInArbitration,
+    
+    #[msg("Wallet is already in the juror pool")]
+    JurorAlreadyRegistered,
+    
+    #[msg("Juror pool is full")]
+    JurorPoolFull,
+    
+    #[msg("Not enough eligible jurors to summon a jury")]
+    JurorPoolTooSmall,
+    
+    #[msg("Juror still sits on an unresolved dispute")]
+    JurorStakeLocked,
+    
+    #[msg("Signer was not selected for this jury")]
+    NotSelectedJuror,
+    
+    #[msg("This voting phase is not open")]
+    VotingPhaseClosed,
+    
+    #[msg("Juror has already voted in this phase")]
+    VoteAlreadyCast,
+    
+    #[msg("Revealed vote does not match the commitment")]
+    InvalidVoteReveal,
+    
+    #[msg("Jury voting is still in progress")]
+    VotingInProgress,
+    
+    #[msg("Disputed amount must be non-zero and within the unfrozen claimable commission")]
+    InvalidDisputeAmount,
+    
+    #[msg("Merchant is not active")]
+    MerchantInactive,
+    
+    #[msg("Close window has not ended and tokens are still outstanding")]
+    CloseWindowNotExpired,
+    
+    #[msg("Merchant response window for this dispute is still open")]
+    DisputeWindowOpen,
+    
+    #[msg("Geo-fence is not active")]
+    GeoFenceInactive,
+    
+    #[msg("Missing or invalid location attestation")]
+    InvalidAttestation,
+    
+    #[msg("Referral has not expired")]
+    ReferralNotExpired,
+    
+    #[msg("Referral has earned commission that is not settled yet")]
+    OutstandingCommission,
+    
+    #[msg("Session key expiry must be in the future")]
+    InvalidSessionExpiry,
+    
+    #[msg("Signer is not an approved oracle authority")]
+    UnauthorizedOracle,
+    
+    #[msg("Key is already an oracle signer")]
+    OracleSignerAlreadyRegistered,
+    
+    #[msg("Oracle signer registry is full")]
+    OracleRegistryFull,
+    
+    #[msg("Reputation threshold must be between one and the number of oracle signers")]
+    InvalidOracleThreshold,
+    
+    #[msg("Not enough oracle signers approved this reputation update")]
+    OracleThresholdNotMet,
+    
+    #[msg("Merchant config is for a different mint")]
+    MerchantMintMismatch,
+    
+    #[msg("Referral record is not the one attributed to this referrer slot")]
+    ReferralSlotMismatch,
+    
+    #[msg("Token generation already uses the current layout")]
+    GenerationAlreadyMigrated,
+    
+    #[msg("The jury draw slot has not been reached")]
+    JuryDrawNotReady,
+    
+    #[msg("Jurors have already been drawn for this dispute")]
+    JuryAlreadyDrawn,
+    
+    #[msg("Juror stake is below the minimum or does not exceed the evicted juror's")]
+    JurorStakeTooLow,
+    
+    #[msg("Evicted juror is not in the pool")]
+    JurorNotInPool,
 }
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, Burn, burn};
 use crate::state::{
-    merchant_config::MerchantConfig,
-    merchant_bond::MerchantBond,
+    merchant_config::{MerchantConfig, VaultEntry},
+    merchant_bond::{MerchantBond, BondVault},
     token_generation::TokenGeneration,
 };
+use crate::state::merchant_bond::{BOND_UNLOCK_DELAY_SECS, MIN_MERCHANT_BOND_LAMPORTS};
+use crate::instructions::burn_tokens::debit_for_burn;
 use crate::errors::ViralSyncError;
 
 // Example constants matching ARCHITECTURE_V4
@@ -21,42 +25,209 @@ pub struct MerchantCloseInitiated {
 pub struct MerchantClosed {
     pub merchant: Pubkey,
     pub mint: Pubkey,
+    pub vault_entries_closed: u8,
+}
+
+#[event]
+pub struct MerchantCloseCancelled {
+    pub merchant: Pubkey,
+    pub mint: Pubkey,
+    pub cancelled_at: i64,
 }
 
 #[event]
 pub struct BondShareRedeemed {
     pub holder: Pubkey,
     pub lamports: u64,
+    pub tokens_burned: u64,
+    pub remaining_supply: u64,
+}
+
+#[event]
+pub struct BondDeposited {
+    pub merchant: Pubkey,
+    pub amount: u64,
+    pub bonded_lamports: u64,
+}
+
+#[event]
+pub struct BondUnlockRequested {
+    pub merchant: Pubkey,
+    pub withdrawable_at: i64,
+}
+
+#[event]
+pub struct BondWithdrawn {
+    pub merchant: Pubkey,
+    pub amount: u64,
+    pub bonded_lamports: u64,
 }
 
 #[derive(Accounts)]
-pub struct WithdrawBond<'info> {
+pub struct InitMerchantBond<'info> {
+    #[account(
+        seeds = [b"merchant_v4", merchant_config.mint.as_ref()],
+        bump = merchant_config.bump,
+        has_one = merchant
+    )]
+    pub merchant_config: Account<'info, MerchantConfig>,
+    
+    #[account(
+        init,
+        payer = merchant,
+        space = MerchantBond::LEN,
+        seeds = [b"merchant_bond", merchant_config.mint.as_ref()],
+        bump
+    )]
+    pub merchant_bond: Account<'info, MerchantBond>,
+    
+    #[account(
+        init,
+        payer = merchant,
+        space = BondVault::LEN,
+        seeds = [b"bond_vault", merchant_bond.key().as_ref()],
+        bump
+    )]
+    pub bond_vault: Account<'info, BondVault>,
+    
     #[account(mut)]
+    pub merchant: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_merchant_bond(ctx: Context<InitMerchantBond>) -> Result<()> {
+    let bond = &mut ctx.accounts.merchant_bond;
+    bond.bump = ctx.bumps.merchant_bond;
+    bond.merchant = ctx.accounts.merchant.key();
+    bond.bonded_lamports = 0;
+    bond.min_required_lamports = MIN_MERCHANT_BOND_LAMPORTS;
+    bond.is_locked = true;
+    bond.unlock_requested_at = 0;
+    bond.vault_bump = ctx.bumps.bond_vault;
+    bond.redeemed_lamports = 0;
+    bond.redeemed_tokens = 0;
+    bond.mint = ctx.accounts.merchant_config.mint;
+    
+    let vault = &mut ctx.accounts.bond_vault;
+    vault.bump = ctx.bumps.bond_vault;
+    vault.merchant_bond = bond.key();
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DepositBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"merchant_bond", merchant_bond.mint.as_ref()],
+        bump = merchant_bond.bump,
+        has_one = merchant
+    )]
     pub merchant_bond: Account<'info, MerchantBond>,
     
+    #[account(mut, seeds = [b"bond_vault", merchant_bond.key().as_ref()], bump = merchant_bond.vault_bump)]
+    pub bond_vault: Account<'info, BondVault>,
+    
+    #[account(mut)]
     pub merchant: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
-pub fn withdraw_bond(ctx: Context<WithdrawBond>, amount: u64) -> Result<()> {
+pub fn deposit_bond(ctx: Context<DepositBond>, amount: u64) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.merchant.to_account_info(),
+        to: ctx.accounts.bond_vault.to_account_info(),
+    };
+    transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts), amount)?;
+    
     let bond = &mut ctx.accounts.merchant_bond;
+    bond.deposit(amount)?;
     
-    require!(!bond.is_locked, ViralSyncError::TokensExpired); // Reused Missing errors -> Map generic AccessDenied in prod
-    require!(
-        bond.bonded_lamports.saturating_sub(amount) >= bond.min_required_lamports,
-        ViralSyncError::InsufficientBalance
-    );
+    emit!(BondDeposited { merchant: bond.merchant, amount, bonded_lamports: bond.bonded_lamports });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestBondUnlock<'info> {
+    #[account(
+        seeds = [b"merchant_v4", merchant_config.mint.as_ref()],
+        bump = merchant_config.bump,
+        has_one = merchant
+    )]
+    pub merchant_config: Account<'info, MerchantConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"merchant_bond", merchant_config.mint.as_ref()],
+        bump = merchant_bond.bump,
+        has_one = merchant
+    )]
+    pub merchant_bond: Account<'info, MerchantBond>,
+    
+    pub merchant: Signer<'info>,
+}
+
+pub fn request_bond_unlock(ctx: Context<RequestBondUnlock>) -> Result<()> {
+    // A closing merchant's bond is reserved for holders until finalize_close_merchant
+    require!(ctx.accounts.merchant_config.close_initiated_at == 0, ViralSyncError::MerchantSunset);
     
-    // Simulate Time Lock Check natively 
-    // require!(Clock::get()?.unix_timestamp > bond.unlock_requested_at + 172800, ViralSyncError::AccessDenied);
+    let now = Clock::get()?.unix_timestamp;
+    let bond = &mut ctx.accounts.merchant_bond;
+    bond.request_unlock(now);
     
-    bond.bonded_lamports -= amount;
-    // Native sublamports from bond vault to merchant...
+    emit!(BondUnlockRequested {
+        merchant: bond.merchant,
+        withdrawable_at: now.saturating_add(BOND_UNLOCK_DELAY_SECS),
+    });
     Ok(())
 }
 
 #[derive(Accounts)]
-pub struct InitiateCloseMerchant<'info> {
+pub struct WithdrawBond<'info> {
+    #[account(
+        seeds = [b"merchant_v4", merchant_config.mint.as_ref()],
+        bump = merchant_config.bump,
+        has_one = merchant
+    )]
+    pub merchant_config: Account<'info, MerchantConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"merchant_bond", merchant_config.mint.as_ref()],
+        bump = merchant_bond.bump,
+        has_one = merchant
+    )]
+    pub merchant_bond: Account<'info, MerchantBond>,
+    
+    #[account(mut, seeds = [b"bond_vault", merchant_bond.key().as_ref()], bump = merchant_bond.vault_bump)]
+    pub bond_vault: Account<'info, BondVault>,
+    
     #[account(mut)]
+    pub merchant: Signer<'info>,
+}
+
+pub fn withdraw_bond(ctx: Context<WithdrawBond>, amount: u64) -> Result<()> {
+    // An unlock requested before initiate_close_merchant cannot drain the bond mid-close
+    require!(ctx.accounts.merchant_config.close_initiated_at == 0, ViralSyncError::MerchantSunset);
+    
+    let bond = &mut ctx.accounts.merchant_bond;
+    bond.withdraw(amount, Clock::get()?.unix_timestamp)?;
+    
+    pay_from_vault(&ctx.accounts.bond_vault.to_account_info(), &ctx.accounts.merchant.to_account_info(), amount)?;
+    
+    emit!(BondWithdrawn { merchant: bond.merchant, amount, bonded_lamports: bond.bonded_lamports });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitiateCloseMerchant<'info> {
+    #[account(
+        mut,
+        seeds = [b"merchant_v4", merchant_config.mint.as_ref()],
+        bump = merchant_config.bump,
+        has_one = merchant
+    )]
     pub merchant_config: Account<'info, MerchantConfig>,
     
     pub merchant: Signer<'info>,
@@ -64,7 +235,7 @@ pub struct InitiateCloseMerchant<'info> {
 
 pub fn initiate_close_merchant(ctx: Context<InitiateCloseMerchant>) -> Result<()> {
     let config = &mut ctx.accounts.merchant_config;
-    require!(config.is_active, ViralSyncError::TokensExpired); // e.g. AlreadyInactive
+    require!(config.is_active, ViralSyncError::MerchantInactive);
 
     config.is_active = false;
     config.close_initiated_at = Clock::get()?.unix_timestamp;
@@ -80,72 +251,224 @@ pub fn initiate_close_merchant(ctx: Context<InitiateCloseMerchant>) -> Result<()
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct CancelCloseMerchant<'info> {
+    #[account(
+        mut,
+        seeds = [b"merchant_v4", merchant_config.mint.as_ref()],
+        bump = merchant_config.bump,
+        has_one = merchant
+    )]
+    pub merchant_config: Account<'info, MerchantConfig>,
+    
+    // Merchants that never posted a bond have no share redemptions to protect
+    #[account(
+        seeds = [b"merchant_bond", merchant_config.mint.as_ref()],
+        bump = merchant_bond.bump,
+        has_one = merchant
+    )]
+    pub merchant_bond: Option<Account<'info, MerchantBond>>,
+    
+    pub merchant: Signer<'info>,
+}
+
+pub fn cancel_close_merchant(ctx: Context<CancelCloseMerchant>) -> Result<()> {
+    let config = &mut ctx.accounts.merchant_config;
+    let bond = &ctx.accounts.merchant_bond;
+    let now = Clock::get()?.unix_timestamp;
+    
+    require!(!config.is_active && config.close_initiated_at > 0, ViralSyncError::CloseNotInitiated);
+    require!(now <= config.close_window_ends_at, ViralSyncError::CloseWindowEnded);
+    // Holders who already burned for their share cannot be made whole again
+    if let Some(bond) = bond {
+        require!(bond.redeemed_tokens == 0 && bond.redeemed_lamports == 0, ViralSyncError::BondSharesRedeemed);
+    }
+    
+    config.is_active = true;
+    config.close_initiated_at = 0;
+    config.close_window_ends_at = 0;
+    
+    emit!(MerchantCloseCancelled { merchant: config.merchant, mint: config.mint, cancelled_at: now });
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct FinalizeCloseMerchant<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"merchant_v4", merchant_config.mint.as_ref()],
+        bump = merchant_config.bump,
+        has_one = merchant,
+        has_one = mint,
+        close = merchant
+    )]
     pub merchant_config: Account<'info, MerchantConfig>,
+    
+    // Closed with the config so the mint can be bonded again under a new MerchantConfig
+    #[account(
+        mut,
+        seeds = [b"merchant_bond", merchant_config.mint.as_ref()],
+        bump = merchant_bond.bump,
+        has_one = merchant,
+        close = merchant
+    )]
     pub merchant_bond: Account<'info, MerchantBond>,
     
     #[account(mut)]
     pub merchant: Signer<'info>,
     
-    /// CHECK: Target Vault
-    #[account(mut)]
-    pub bond_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"bond_vault", merchant_bond.key().as_ref()],
+        bump = merchant_bond.vault_bump,
+        close = merchant
+    )]
+    pub bond_vault: Account<'info, BondVault>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"gen_v4", merchant_config.mint.as_ref(), treasury_generation.owner.as_ref()],
+        bump = treasury_generation.bump,
+        constraint = treasury_generation.is_treasury @ ViralSyncError::AccessDenied,
+        close = merchant
+    )]
+    pub treasury_generation: Account<'info, TokenGeneration>,
+    
+    // remaining_accounts: the merchant's VaultEntry PDAs, closed alongside the config
 }
 
-pub fn finalize_close_merchant(ctx: Context<FinalizeCloseMerchant>) -> Result<()> {
+pub fn finalize_close_merchant<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeCloseMerchant<'info>>) -> Result<()> {
     let config = &ctx.accounts.merchant_config;
-    let bond = &ctx.accounts.merchant_bond;
+    let bond = &mut ctx.accounts.merchant_bond;
     let now = Clock::get()?.unix_timestamp;
 
-    require!(now > config.close_window_ends_at, ViralSyncError::TokensExpired); // e.g. CloseWindowNotExpired
+    require!(!config.is_active && config.close_initiated_at > 0, ViralSyncError::CloseNotInitiated);
+    // Closing early is fine once every token has been redeemed or burned
+    require!(
+        now > config.close_window_ends_at || ctx.accounts.mint.supply == 0,
+        ViralSyncError::CloseWindowNotExpired
+    );
 
+    // Whatever holders did not redeem during the close window returns to the merchant; the vault's
+    // rent follows when it is closed
     let remaining_bond = bond.bonded_lamports;
-    // **ctx.accounts.merchant.try_borrow_mut_lamports()? += remaining_bond;
-    // **ctx.accounts.bond_account.try_borrow_mut_lamports()? -= remaining_bond;
+    bond.bonded_lamports = 0;
+    pay_from_vault(&ctx.accounts.bond_vault.to_account_info(), &ctx.accounts.merchant.to_account_info(), remaining_bond)?;
+
+    let merchant = ctx.accounts.merchant.to_account_info();
+    for info in ctx.remaining_accounts.iter() {
+        // Account::try_from enforces program ownership and the VaultEntry discriminator
+        let entry: Account<VaultEntry> = Account::try_from(info)?;
+        require!(entry.merchant == merchant.key(), ViralSyncError::AccessDenied);
+        let seeds: &[&[u8]] = &[b"vault_entry", config.mint.as_ref(), entry.vault.as_ref(), &[entry.bump]];
+        require!(
+            Pubkey::create_program_address(seeds, &crate::ID).is_ok_and(|pda| pda == info.key()),
+            ViralSyncError::AccessDenied
+        );
+        entry.close(merchant.clone())?;
+    }
 
-    emit!(MerchantClosed { merchant: config.merchant, mint: config.mint });
+    emit!(MerchantClosed {
+        merchant: config.merchant,
+        mint: config.mint,
+        vault_entries_closed: ctx.remaining_accounts.len() as u8,
+    });
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct RedeemBondShare<'info> {
+    #[account(mut, seeds = [b"merchant_v4", merchant_config.mint.as_ref()], bump = merchant_config.bump)]
     pub merchant_config: Account<'info, MerchantConfig>,
     
+    #[account(
+        mut,
+        seeds = [b"gen_v4", merchant_config.mint.as_ref(), holder.key().as_ref()],
+        bump = holder_generation.bump
+    )]
     pub holder_generation: Account<'info, TokenGeneration>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = holder_ata.owner == holder.key() @ ViralSyncError::AccessDenied,
+        constraint = holder_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub holder_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut, address = merchant_config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"merchant_bond", merchant_config.mint.as_ref()],
+        bump = merchant_bond.bump
+    )]
     pub merchant_bond: Account<'info, MerchantBond>,
     
     #[account(mut)]
     pub holder: Signer<'info>,
     
-    /// CHECK: Target Vault
-    #[account(mut)]
-    pub bond_account: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"bond_vault", merchant_bond.key().as_ref()], bump = merchant_bond.vault_bump)]
+    pub bond_vault: Account<'info, BondVault>,
+    
+    /// CHECK: PDA owning the merchant treasury's token account
+    #[account(seeds = [b"treasury", mint.key().as_ref()], bump)]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    // Tokens still in the treasury were never handed out, so the bond does not back them
+    #[account(
+        constraint = treasury_ata.owner == treasury_authority.key() @ ViralSyncError::AccessDenied,
+        constraint = treasury_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn redeem_bond_share(ctx: Context<RedeemBondShare>) -> Result<()> {
-    let config = &ctx.accounts.merchant_config;
-    let gen = &ctx.accounts.holder_generation;
-    let bond = &mut ctx.accounts.merchant_bond;
+    require!(!ctx.accounts.merchant_config.is_active, ViralSyncError::CloseNotInitiated);
+
+    // The whole token balance is burned, so a holder can only redeem once per token
+    let holder_tokens = ctx.accounts.holder_ata.amount;
+    require!(holder_tokens > 0, ViralSyncError::InsufficientBalance);
 
-    require!(!config.is_active, ViralSyncError::TokensExpired);
+    // Pro-rata over what holders still have outstanding: earlier redemptions shrink the bond and
+    // the supply together, so later holders get the same rate
+    let remaining_supply = ctx.accounts.mint.supply.saturating_sub(ctx.accounts.treasury_ata.amount);
+    let bond_share = ctx.accounts.merchant_bond.pro_rata_share(holder_tokens, remaining_supply);
 
-    let holder_tokens = gen.gen1_balance + gen.gen2_balance + gen.dead_balance;
-    let pct_of_supply = (holder_tokens as u128)
-        .checked_mul(1_000_000).unwrap()
-        .checked_div(config.current_supply.max(1) as u128).unwrap() as u64;
+    debit_for_burn(&mut ctx.accounts.holder_generation, holder_tokens)?;
 
-    let bond_share = (bond.bonded_lamports as u128)
-        .checked_mul(pct_of_supply as u128).unwrap()
-        .checked_div(1_000_000).unwrap() as u64;
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.holder_ata.to_account_info(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), holder_tokens)?;
 
-    bond.bonded_lamports -= bond_share;
-    // **ctx.accounts.holder.try_borrow_mut_lamports()? += bond_share;
-    // **ctx.accounts.bond_account.try_borrow_mut_lamports()? -= bond_share;
+    let config = &mut ctx.accounts.merchant_config;
+    config.current_supply = config.current_supply.saturating_sub(holder_tokens);
+
+    let bond = &mut ctx.accounts.merchant_bond;
+    bond.record_share_redemption(bond_share, holder_tokens)?;
+    pay_from_vault(&ctx.accounts.bond_vault.to_account_info(), &ctx.accounts.holder.to_account_info(), bond_share)?;
+
+    emit!(BondShareRedeemed {
+        holder: ctx.accounts.holder.key(),
+        lamports: bond_share,
+        tokens_burned: holder_tokens,
+        remaining_supply: remaining_supply - holder_tokens,
+    });
+    Ok(())
+}
 
-    emit!(BondShareRedeemed { holder: gen.owner, lamports: bond_share });
+// The vault is program-owned, so lamports move by direct debit rather than a system transfer
+pub fn pay_from_vault(vault: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let vault_lamports = vault.lamports().checked_sub(amount).ok_or(ViralSyncError::InsufficientBalance)?;
+    let to_lamports = to.lamports().checked_add(amount).ok_or(ViralSyncError::MathOverflow)?;
+    **vault.try_borrow_mut_lamports()? = vault_lamports;
+    **to.try_borrow_mut_lamports()? = to_lamports;
     Ok(())
 }
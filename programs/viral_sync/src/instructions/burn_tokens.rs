@@ -20,8 +20,22 @@ pub struct BurnTokens<'info> {
 }
 
 pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
-    let gen = &mut ctx.accounts.token_generation;
+    debit_for_burn(&mut ctx.accounts.token_generation, amount)?;
     
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.owner_ata.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    burn(cpi_ctx, amount)?;
+    
+    Ok(())
+}
+
+// Shared with burn_tokens_with_session
+pub(crate) fn debit_for_burn(gen: &mut TokenGeneration, amount: u64) -> Result<()> {
     // Reverse FIFO: consume dead first, then gen2, then gen1.
     // Maximizes user's referral and redemption attribution power by dropping useless tokens first.
     let from_dead = amount.min(gen.dead_balance);
@@ -41,14 +55,5 @@ pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
     gen.gen2_balance -= from_gen2;
     gen.gen1_balance -= from_gen1;
     
-    let cpi_accounts = Burn {
-        mint: ctx.accounts.mint.to_account_info(),
-        from: ctx.accounts.owner_ata.to_account_info(),
-        authority: ctx.accounts.owner.to_account_info(),
-    };
-    
-    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-    burn(cpi_ctx, amount)?;
-    
     Ok(())
 }
@@ -7,66 +7,109 @@ use crate::state::{
 };
 use crate::errors::ViralSyncError;
 
+// Accounts moving a referrer's claimable commission out of the merchant treasury. Shared by the
+// owner-signed claim_commission and claim_commission_with_session.
 #[derive(Accounts)]
-pub struct ClaimCommission<'info> {
+pub struct CommissionPayout<'info> {
     #[account(mut)]
     pub commission_ledger: Account<'info, CommissionLedger>,
     
+    // Referrer's generation on this mint, credited with the claim in its activity summary
+    #[account(
+        mut,
+        constraint = referrer_generation.owner == commission_ledger.referrer @ ViralSyncError::ReferrerGenerationMismatch,
+        constraint = referrer_generation.mint == commission_ledger.mint @ ViralSyncError::ReferrerGenerationMismatch
+    )]
+    pub referrer_generation: Account<'info, TokenGeneration>,
+    
+    #[account(seeds = [b"merchant_v4", mint.key().as_ref()], bump = merchant_config.bump)]
     pub merchant_config: Account<'info, MerchantConfig>,
     
-    // Note: PDA verifying treasury seeds omitted for brevity but required in real implementation
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"gen_v4", mint.key().as_ref(), treasury_ata.key().as_ref()],
+        bump = treasury_generation.bump,
+        constraint = treasury_generation.is_treasury @ ViralSyncError::AccessDenied
+    )]
     pub treasury_generation: Account<'info, TokenGeneration>,
     
-    /// CHECK: Treasury's ATA Vault handling commission reserves
-    #[account(mut)]
-    pub treasury_ata: UncheckedAccount<'info>,
+    // Treasury's vault holding the commission reserves
+    #[account(
+        mut,
+        constraint = treasury_ata.owner == treasury_signer.key() @ ViralSyncError::AccessDenied,
+        constraint = treasury_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
     
-    #[account(mut)]
+    // Commission only ever lands in the referrer's own account
+    #[account(
+        mut,
+        constraint = referrer_ata.owner == commission_ledger.referrer @ ViralSyncError::AccessDenied,
+        constraint = referrer_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
     pub referrer_ata: InterfaceAccount<'info, TokenAccount>,
     
+    #[account(address = commission_ledger.mint)]
     pub mint: InterfaceAccount<'info, Mint>,
     
-    /// CHECK: PDA Treasury Signer
+    /// CHECK: PDA owning the merchant treasury's token account
+    #[account(seeds = [b"treasury", mint.key().as_ref()], bump)]
     pub treasury_signer: UncheckedAccount<'info>,
     
     pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn claim_commission(ctx: Context<ClaimCommission>) -> Result<()> {
-    let ledger = &mut ctx.accounts.commission_ledger;
-    let config = &ctx.accounts.merchant_config;
-    
-    require!(!ledger.frozen, ViralSyncError::CommissionFrozenDictated);
-    
-    let gross_claimable = ledger.claimable;
-    require!(gross_claimable > 0, ViralSyncError::NothingToClaim);
+#[derive(Accounts)]
+pub struct ClaimCommission<'info> {
+    #[account(address = payout.commission_ledger.referrer @ ViralSyncError::AccessDenied)]
+    pub referrer: Signer<'info>,
     
-    // Adjust for Token-2022 transfer fees so real amount received exactly matches earned
-    // gross_to_send = net_receive / (1 - fee_rate)
-    let fee_bps = config.transfer_fee_bps as u64;
-    let gross_to_send = (gross_claimable as u128)
-        .checked_mul(10_000).unwrap()
-        .checked_div(10_000 - fee_bps as u128).unwrap() as u64;
+    pub payout: CommissionPayout<'info>,
+}
+
+pub fn claim_commission(ctx: Context<ClaimCommission>) -> Result<()> {
+    ctx.accounts.payout.claim(ctx.bumps.payout.treasury_signer)
+}
+
+impl<'info> CommissionPayout<'info> {
+    pub fn claim(&mut self, treasury_bump: u8) -> Result<()> {
+        let ledger = &mut self.commission_ledger;
+        let config = &self.merchant_config;
         
-    // Execute transfer. Because the treasury is sending, its hook flags (is_treasury = true) 
-    // will tag the incoming tokens on the referrer side identically as Gen-1 tokens issuance.
-    
-    let cpi_accounts = TransferChecked {
-        from: ctx.accounts.treasury_ata.to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        to: ctx.accounts.referrer_ata.to_account_info(),
-        authority: ctx.accounts.treasury_signer.to_account_info(),
-    };
-    
-    // Needs PDA signer seeds here in full deployment
-    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-    transfer_checked(cpi_ctx, gross_to_send, ctx.accounts.mint.decimals)?;
-    
-    ledger.total_claimed = ledger.total_claimed.checked_add(gross_claimable).unwrap();
-    ledger.claimable = 0;
-    // We intentionally wipe fractional dust on claims explicitly so manual intervention ensures alignment.
-    ledger.dust_tenths_accumulated = 0; 
-    
-    Ok(())
+        require!(!ledger.frozen, ViralSyncError::CommissionFrozenDictated);
+        
+        let gross_claimable = ledger.claimable;
+        require!(gross_claimable > 0, ViralSyncError::NothingToClaim);
+        
+        // Adjust for Token-2022 transfer fees so real amount received exactly matches earned
+        // gross_to_send = net_receive / (1 - fee_rate)
+        let fee_bps = config.transfer_fee_bps as u64;
+        let gross_to_send = (gross_claimable as u128)
+            .checked_mul(10_000).unwrap()
+            .checked_div(10_000 - fee_bps as u128).unwrap() as u64;
+            
+        // Execute transfer. Because the treasury is sending, its hook flags (is_treasury = true) 
+        // will tag the incoming tokens on the referrer side identically as Gen-1 tokens issuance.
+        
+        let cpi_accounts = TransferChecked {
+            from: self.treasury_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.referrer_ata.to_account_info(),
+            authority: self.treasury_signer.to_account_info(),
+        };
+        
+        let mint_key = self.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", mint_key.as_ref(), &[treasury_bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        transfer_checked(cpi_ctx, gross_to_send, self.mint.decimals)?;
+        
+        ledger.total_claimed = ledger.total_claimed.checked_add(gross_claimable).unwrap();
+        ledger.claimable = 0;
+        // We intentionally wipe fractional dust on claims explicitly so manual intervention ensures alignment.
+        ledger.dust_tenths_accumulated = 0; 
+        
+        self.referrer_generation.record_commission_claim(gross_claimable);
+        
+        Ok(())
+    }
 }
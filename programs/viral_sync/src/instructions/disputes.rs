@@ -1,12 +1,34 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked, Burn, burn};
 use crate::state::{
-    dispute_record::{DisputeRecord, DisputeStatus},
+    dispute_record::{DisputeRecord, DisputeStatus, DisputeEscrow, Arbitration},
+    jury::{DisputeVote, MAX_JUROR_POOL},
     commission_ledger::CommissionLedger,
     merchant_reputation::MerchantReputation,
+    referral_record::ReferralRecord,
 };
+use crate::instructions::bond_management::pay_from_vault;
 use crate::errors::ViralSyncError;
 
 pub const DISPUTE_MERCHANT_RESPONSE_SECS: i64 = 1209600; // 14 days
+pub const DISPUTE_UPHELD_REPUTATION_PENALTY: u32 = 500;
+pub const DISPUTE_STAKE_LAMPORTS: u64 = 100_000_000; // 0.1 SOL
+pub const DISPUTE_CRANK_BOUNTY_LAMPORTS: u64 = 5_000_000; // 0.005 SOL, paid out of the stake to whoever resolves an expired dispute
+
+// Split of a forfeited commission; the merchant treasury keeps the remainder
+pub const DISPUTE_WATCHDOG_REWARD_BPS: u64 = 5000;
+pub const DISPUTE_BURN_BPS: u64 = 2500;
+
+#[event]
+pub struct DisputeRaised {
+    pub merchant: Pubkey,
+    pub referral: Pubkey,
+    pub watchdog: Pubkey,
+    pub disputed_amount: u64,
+    pub stake_lamports: u64,
+    pub total_frozen: u64,
+}
 
 #[event]
 pub struct DisputeAutoUpheld {
@@ -14,66 +36,236 @@ pub struct DisputeAutoUpheld {
     pub referral: Pubkey,
     pub disputed_amount: u64,
     pub watchdog_reward: u64,
+    pub cranker: Pubkey,
+    pub crank_bounty: u64,
+}
+
+#[event]
+pub struct DisputeResponded {
+    pub merchant: Pubkey,
+    pub referral: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub responded_at: i64,
 }
 
+#[event]
+pub struct DisputeResolved {
+    pub merchant: Pubkey,
+    pub referral: Pubkey,
+    pub status: DisputeStatus,
+    pub disputed_amount: u64,
+    pub watchdog_reward: u64,
+    pub stake_slashed: u64,
+}
+
+#[event]
+#[derive(Clone)]
+pub struct DisputeFundsSettled {
+    pub dispute: Pubkey,
+    pub watchdog: Pubkey,
+    pub merchant: Pubkey,
+    pub disputed_amount: u64,
+    pub watchdog_reward: u64,  // Tokens from the treasury to the watchdog
+    pub treasury_retained: u64, // Tokens left in the merchant treasury
+    pub burned: u64,           // Tokens burned from the treasury
+    pub stake_returned: u64,   // Lamports from the escrow back to the watchdog
+    pub stake_slashed: u64,    // Lamports from the escrow to the merchant
+    pub crank_bounty: u64,     // Lamports from the escrow to the cranker
+}
+
+// Returns (watchdog_reward, treasury_retained, burned)
+pub fn split_slashed_commission(amount: u64) -> (u64, u64, u64) {
+    let reward = ((amount as u128) * DISPUTE_WATCHDOG_REWARD_BPS as u128 / 10_000) as u64;
+    let burned = ((amount as u128) * DISPUTE_BURN_BPS as u128 / 10_000) as u64;
+    (reward, amount - reward - burned, burned)
+}
+
+// ── SETTLEMENT ──────────────────────────────────────────────────────────────
+// Everything a ruling moves funds through; shared by the timeout, arbiter and jury paths
 #[derive(Accounts)]
-pub struct ResolveExpiredDispute<'info> {
-    #[account(mut)]
+pub struct DisputeSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [b"dispute", dispute_record.referral.as_ref(), dispute_record.watchdog.as_ref()],
+        bump = dispute_record.bump
+    )]
     pub dispute_record: Account<'info, DisputeRecord>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"dispute_escrow", dispute_record.key().as_ref()],
+        bump = dispute_record.escrow_bump
+    )]
+    pub dispute_escrow: Account<'info, DisputeEscrow>,
+    
+    #[account(address = dispute_record.referral)]
+    pub referral_record: Account<'info, ReferralRecord>,
+    
+    // Must be the ledger the dispute froze
+    #[account(
+        mut,
+        constraint = commission_ledger.referrer == referral_record.referrer @ ViralSyncError::AccessDenied,
+        constraint = commission_ledger.merchant == referral_record.merchant @ ViralSyncError::AccessDenied,
+        constraint = commission_ledger.mint == referral_record.mint @ ViralSyncError::AccessDenied
+    )]
     pub commission_ledger: Account<'info, CommissionLedger>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = merchant_reputation.merchant == dispute_record.merchant @ ViralSyncError::AccessDenied
+    )]
     pub merchant_reputation: Account<'info, MerchantReputation>,
     
-    /// CHECK: Stake Escrow PDA return targets 
-    #[account(mut)]
-    pub dispute_escrow: UncheckedAccount<'info>,
+    /// CHECK: Recorded watchdog; receives the reward, the returned stake and the escrow rent
+    #[account(mut, address = dispute_record.watchdog)]
+    pub watchdog: UncheckedAccount<'info>,
+    
+    /// CHECK: Recorded merchant; receives a slashed stake
+    #[account(mut, address = dispute_record.merchant)]
+    pub merchant: UncheckedAccount<'info>,
+    
+    #[account(mut, address = commission_ledger.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
     
+    /// CHECK: PDA owning the merchant treasury's token account
+    #[account(seeds = [b"treasury", mint.key().as_ref()], bump)]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = treasury_ata.owner == treasury_authority.key() @ ViralSyncError::AccessDenied,
+        constraint = treasury_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = watchdog_ata.owner == dispute_record.watchdog @ ViralSyncError::AccessDenied,
+        constraint = watchdog_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub watchdog_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DisputeSettlement<'info> {
+    // Upheld: the forfeited commission is split and the stake goes back to the watchdog.
+    // Dismissed: the commission is released and the stake goes to the merchant.
+    // A cranker, if any, is paid its bounty out of the returned stake.
+    pub(crate) fn settle(&mut self, uphold: bool, treasury_bump: u8, cranker: Option<&AccountInfo<'info>>) -> Result<DisputeFundsSettled> {
+        let dispute = &self.dispute_record;
+        let escrow = self.dispute_escrow.to_account_info();
+        let mut flows = DisputeFundsSettled {
+            dispute: dispute.key(),
+            watchdog: dispute.watchdog,
+            merchant: dispute.merchant,
+            disputed_amount: dispute.disputed_amount,
+            watchdog_reward: 0,
+            treasury_retained: 0,
+            burned: 0,
+            stake_returned: 0,
+            stake_slashed: 0,
+            crank_bounty: 0,
+        };
+        
+        if uphold {
+            let forfeited = self.commission_ledger.slash_frozen(dispute.disputed_amount);
+            let (reward, retained, burned) = split_slashed_commission(forfeited);
+            
+            let mint_key = self.mint.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", mint_key.as_ref(), &[treasury_bump]]];
+            if reward > 0 {
+                let cpi_accounts = TransferChecked {
+                    from: self.treasury_ata.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.watchdog_ata.to_account_info(),
+                    authority: self.treasury_authority.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+                transfer_checked(cpi_ctx, reward, self.mint.decimals)?;
+            }
+            if burned > 0 {
+                let cpi_accounts = Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.treasury_ata.to_account_info(),
+                    authority: self.treasury_authority.to_account_info(),
+                };
+                burn(CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds), burned)?;
+            }
+            
+            let crank_bounty = match cranker {
+                Some(cranker) => {
+                    let bounty = DISPUTE_CRANK_BOUNTY_LAMPORTS.min(dispute.stake_lamports);
+                    pay_from_vault(&escrow, cranker, bounty)?;
+                    bounty
+                }
+                None => 0,
+            };
+            let stake_returned = dispute.stake_lamports - crank_bounty;
+            pay_from_vault(&escrow, &self.watchdog, stake_returned)?;
+            
+            flows.disputed_amount = forfeited;
+            flows.watchdog_reward = reward;
+            flows.treasury_retained = retained;
+            flows.burned = burned;
+            flows.stake_returned = stake_returned;
+            flows.crank_bounty = crank_bounty;
+        } else {
+            self.commission_ledger.release_frozen(dispute.disputed_amount);
+            pay_from_vault(&escrow, &self.merchant, dispute.stake_lamports)?;
+            flows.stake_slashed = dispute.stake_lamports;
+        }
+        // The watchdog funded both accounts' rent. Closing the record also frees its
+        // [dispute, referral, watchdog] seeds, so the same watchdog can dispute this referral again.
+        self.dispute_escrow.close(self.watchdog.to_account_info())?;
+        self.dispute_record.close(self.watchdog.to_account_info())?;
+        
+        emit!(flows.clone());
+        Ok(flows)
+    }
+}
+
+#[derive(Accounts)]
+pub struct ResolveExpiredDispute<'info> {
+    // Anyone may crank an expired dispute; the reward still goes to the recorded watchdog
     #[account(mut)]
-    pub watchdog: Signer<'info>, 
+    pub cranker: Signer<'info>,
+    
+    pub settlement: DisputeSettlement<'info>,
 }
 
 // Target Fix for Architecture documentation (D3) Auto Resolve
 pub fn resolve_expired_dispute(ctx: Context<ResolveExpiredDispute>) -> Result<()> {
-    let dispute = &mut ctx.accounts.dispute_record;
+    let settlement = &mut ctx.accounts.settlement;
+    let dispute = &mut settlement.dispute_record;
     let now = Clock::get()?.unix_timestamp;
     
-    require!(
-        dispute.status == DisputeStatus::Pending,
-        ViralSyncError::TokensExpired // Generic "InvalidState" map 
-    );
+    require!(dispute.status == DisputeStatus::Pending, ViralSyncError::DisputeNotPending);
     
     // Core Engine Rule: Negligent merchant loses instantly over 14 days
     require!(
         now > dispute.raised_at + DISPUTE_MERCHANT_RESPONSE_SECS,
-        ViralSyncError::TokensExpired // e.g., DisputeStillWithinResponseWindow
+        ViralSyncError::DisputeWindowOpen
     );
     
     dispute.status = DisputeStatus::UpheldByTimeout;
-    
-    let commission_ledger = &mut ctx.accounts.commission_ledger;
-    let disputed_amount = commission_ledger.frozen_amount;
-    let watchdog_share = disputed_amount / 2;
-    
-    commission_ledger.frozen = false;
-    commission_ledger.claimable = commission_ledger.claimable.saturating_sub(disputed_amount);
+    dispute.resolved_at = Some(now);
     
     // Reputation execution logic natively lowering the scores massively for neglect
-    let rep = &mut ctx.accounts.merchant_reputation;
+    let rep = &mut settlement.merchant_reputation;
     rep.timeout_disputes += 1;
-    rep.reputation_score = rep.reputation_score.saturating_sub(500);
+    rep.reputation_score = rep.reputation_score.saturating_sub(DISPUTE_UPHELD_REPUTATION_PENALTY);
     
-    // Returning the Watchdog's active stake 
-    // **ctx.accounts.dispute_escrow.try_borrow_mut_lamports()? -= dispute.stake_lamports;
-    // **ctx.accounts.watchdog.try_borrow_mut_lamports()? += dispute.stake_lamports;
+    let cranker = ctx.accounts.cranker.to_account_info();
+    let flows = settlement.settle(true, ctx.bumps.settlement.treasury_authority, Some(&cranker))?;
     
     emit!(DisputeAutoUpheld {
-        merchant: dispute.merchant,
-        referral: dispute.referral,
-        disputed_amount,
-        watchdog_reward: watchdog_share,
+        merchant: flows.merchant,
+        referral: settlement.dispute_record.referral,
+        disputed_amount: flows.disputed_amount,
+        watchdog_reward: flows.watchdog_reward,
+        cranker: cranker.key(),
+        crank_bounty: flows.crank_bounty,
     });
     
     Ok(())
@@ -81,24 +273,230 @@ pub fn resolve_expired_dispute(ctx: Context<ResolveExpiredDispute>) -> Result<()
 
 #[derive(Accounts)]
 pub struct RaiseDispute<'info> {
-    #[account(mut)]
+    #[account(
+        init,
+        payer = watchdog,
+        space = DisputeRecord::LEN,
+        seeds = [b"dispute", referral_record.key().as_ref(), watchdog.key().as_ref()],
+        bump
+    )]
     pub dispute_record: Account<'info, DisputeRecord>,
     
-    #[account(mut)]
+    #[account(
+        init,
+        payer = watchdog,
+        space = DisputeEscrow::LEN,
+        seeds = [b"dispute_escrow", dispute_record.key().as_ref()],
+        bump
+    )]
+    pub dispute_escrow: Account<'info, DisputeEscrow>,
+    
+    pub referral_record: Account<'info, ReferralRecord>,
+    
+    #[account(
+        mut,
+        constraint = commission_ledger.referrer == referral_record.referrer @ ViralSyncError::AccessDenied,
+        constraint = commission_ledger.merchant == referral_record.merchant @ ViralSyncError::AccessDenied,
+        constraint = commission_ledger.mint == referral_record.mint @ ViralSyncError::AccessDenied
+    )]
     pub commission_ledger: Account<'info, CommissionLedger>,
     
     #[account(mut)]
     pub watchdog: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
+// One open dispute per (referral, watchdog); the stake sits in the escrow until resolution
 pub fn raise_dispute(ctx: Context<RaiseDispute>, amount: u64) -> Result<()> {
+    let ledger = &mut ctx.accounts.commission_ledger;
+    ledger.freeze(amount)?;
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.watchdog.to_account_info(),
+        to: ctx.accounts.dispute_escrow.to_account_info(),
+    };
+    transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts), DISPUTE_STAKE_LAMPORTS)?;
+    
+    let dispute_key = ctx.accounts.dispute_record.key();
+    let escrow = &mut ctx.accounts.dispute_escrow;
+    escrow.bump = ctx.bumps.dispute_escrow;
+    escrow.dispute = dispute_key;
+    
+    let referral = &ctx.accounts.referral_record;
     let dispute = &mut ctx.accounts.dispute_record;
+    dispute.bump = ctx.bumps.dispute_record;
+    dispute.merchant = referral.merchant;
+    dispute.referral = referral.key();
+    dispute.watchdog = ctx.accounts.watchdog.key();
     dispute.status = DisputeStatus::Pending;
+    dispute.stake_lamports = DISPUTE_STAKE_LAMPORTS;
     dispute.raised_at = Clock::get()?.unix_timestamp;
+    dispute.resolved_at = None;
+    dispute.evidence_hash = None;
+    dispute.responded_at = None;
+    dispute.disputed_amount = amount;
+    dispute.escrow_bump = ctx.bumps.dispute_escrow;
     
-    let ledger = &mut ctx.accounts.commission_ledger;
-    ledger.frozen = true;
-    ledger.frozen_amount = amount;
+    emit!(DisputeRaised {
+        merchant: dispute.merchant,
+        referral: dispute.referral,
+        watchdog: dispute.watchdog,
+        disputed_amount: amount,
+        stake_lamports: dispute.stake_lamports,
+        total_frozen: ledger.frozen_amount,
+    });
+    
+    Ok(())
+}
+
+// ── MERCHANT RESPONSE ───────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct RespondToDispute<'info> {
+    #[account(mut, has_one = merchant)]
+    pub dispute_record: Account<'info, DisputeRecord>,
+    
+    pub merchant: Signer<'info>,
+}
+
+// Answering inside the window stops the timeout path and hands the dispute to the arbiter
+pub fn respond_to_dispute(ctx: Context<RespondToDispute>, evidence_hash: [u8; 32]) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute_record;
+    let now = Clock::get()?.unix_timestamp;
+    
+    require!(dispute.status == DisputeStatus::Pending, ViralSyncError::DisputeNotPending);
+    require!(
+        now <= dispute.raised_at + DISPUTE_MERCHANT_RESPONSE_SECS,
+        ViralSyncError::DisputeResponseWindowClosed
+    );
+    
+    dispute.status = DisputeStatus::InArbitration;
+    dispute.evidence_hash = Some(evidence_hash);
+    dispute.responded_at = Some(now);
+    
+    emit!(DisputeResponded {
+        merchant: dispute.merchant,
+        referral: dispute.referral,
+        evidence_hash,
+        responded_at: now,
+    });
+    
+    Ok(())
+}
+
+// ── ARBITRATION ─────────────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct InitArbitration<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Arbitration::LEN,
+        seeds = [b"arbitration"],
+        bump
+    )]
+    pub arbitration: Account<'info, Arbitration>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    // Only the program's upgrade authority may bootstrap arbitration
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ViralSyncError::AccessDenied)]
+    pub program: Program<'info, crate::program::ViralSync>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ViralSyncError::AccessDenied)]
+    pub program_data: Account<'info, ProgramData>,
     
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_arbitration(ctx: Context<InitArbitration>, arbiter: Pubkey) -> Result<()> {
+    let arbitration = &mut ctx.accounts.arbitration;
+    arbitration.bump = ctx.bumps.arbitration;
+    arbitration.admin = ctx.accounts.admin.key();
+    arbitration.arbiter = arbiter;
+    arbitration.juror_count = 0;
+    arbitration.jurors = [Pubkey::default(); MAX_JUROR_POOL];
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetArbiter<'info> {
+    #[account(mut, seeds = [b"arbitration"], bump = arbitration.bump, has_one = admin)]
+    pub arbitration: Account<'info, Arbitration>,
+    pub admin: Signer<'info>,
+}
+
+pub fn set_arbiter(ctx: Context<SetArbiter>, arbiter: Pubkey) -> Result<()> {
+    ctx.accounts.arbitration.arbiter = arbiter;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(seeds = [b"arbitration"], bump = arbitration.bump, has_one = arbiter)]
+    pub arbitration: Account<'info, Arbitration>,
+    
+    pub arbiter: Signer<'info>,
+    
+    pub settlement: DisputeSettlement<'info>,
+    
+    /// CHECK: The dispute's vote PDA, if one was summoned; closed in the handler
+    #[account(mut, seeds = [b"dispute_vote", settlement.dispute_record.key().as_ref()], bump)]
+    pub dispute_vote: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives a summoned vote's rent; checked against dispute_vote.payer
+    #[account(mut)]
+    pub vote_payer: UncheckedAccount<'info>,
+}
+
+pub fn resolve_dispute(ctx: Context<ResolveDispute>, uphold: bool) -> Result<()> {
+    require!(
+        ctx.accounts.settlement.dispute_record.status == DisputeStatus::InArbitration,
+        ViralSyncError::DisputeNotInArbitration
+    );
+    
+    // Drawing moves the dispute to InVote, so a vote still open here has no jurors seated yet
+    let vote_info = ctx.accounts.dispute_vote.to_account_info();
+    if vote_info.owner == &crate::ID {
+        let vote = DisputeVote::try_deserialize(&mut &vote_info.try_borrow_data()?[..])?;
+        require!(vote.payer == ctx.accounts.vote_payer.key(), ViralSyncError::AccessDenied);
+        pay_from_vault(&vote_info, &ctx.accounts.vote_payer, vote_info.lamports())?;
+        vote_info.assign(&anchor_lang::system_program::ID);
+        vote_info.realloc(0, false)?;
+    }
+    
+    apply_ruling(
+        &mut ctx.accounts.settlement,
+        uphold,
+        DisputeStatus::UpheldByArbiter,
+        ctx.bumps.settlement.treasury_authority,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+// Shared by the arbiter and jury paths, which record an upheld dispute as `upheld_status`.
+// Upholding forfeits the commission like a timeout, without counting as neglect.
+pub(crate) fn apply_ruling(settlement: &mut DisputeSettlement, uphold: bool, upheld_status: DisputeStatus, treasury_bump: u8, now: i64) -> Result<()> {
+    let dispute = &mut settlement.dispute_record;
+    if uphold {
+        dispute.status = upheld_status;
+        let rep = &mut settlement.merchant_reputation;
+        rep.reputation_score = rep.reputation_score.saturating_sub(DISPUTE_UPHELD_REPUTATION_PENALTY);
+    } else {
+        dispute.status = DisputeStatus::Dismissed;
+    }
+    dispute.resolved_at = Some(now);
+    
+    let flows = settlement.settle(uphold, treasury_bump, None)?;
+    
+    let dispute = &settlement.dispute_record;
+    emit!(DisputeResolved {
+        merchant: dispute.merchant,
+        referral: dispute.referral,
+        status: dispute.status.clone(),
+        disputed_amount: flows.disputed_amount,
+        watchdog_reward: flows.watchdog_reward,
+        stake_slashed: flows.stake_slashed,
+    });
     Ok(())
 }
@@ -1,40 +1,123 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
-use crate::state::token_generation::{TokenGeneration, INBOUND_BUFFER_SIZE, InboundEntry};
+use anchor_spl::token_interface::{
+    Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account,
+};
+use crate::state::{
+    escrow::{Escrow, EscrowTerms, MAX_LINK_DROP_CLAIMERS},
+    merchant_config::MerchantConfig,
+    token_generation::TokenGeneration,
+};
 use crate::errors::ViralSyncError;
 
+// Paid to whoever cranks an expired escrow, out of the escrow account's rent
+pub const ESCROW_HARVEST_BOUNTY_LAMPORTS: u64 = 100_000;
+pub const ESCROW_MIN_DURATION_SECS: i64 = 86400;
+
+#[event]
+pub struct EscrowCreated {
+    pub escrow: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub recipient: Option<Pubkey>,
+    pub has_claim_code: bool,
+    pub max_claimers: u16,
+    pub amount_per_claim: u64,
+}
+
+#[event]
+pub struct EscrowClaimed {
+    pub escrow: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct EscrowHarvested {
+    pub escrow: Pubkey,
+    pub creator: Pubkey,
+    pub caller: Pubkey,
+    pub returned: u64,
+    pub bounty: u64,
+}
+
 #[derive(Accounts)]
+#[instruction(nonce: u64, amount: u64, terms: EscrowTerms)]
 pub struct CreateEscrowShare<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = source_generation.owner == source.key() @ ViralSyncError::AccessDenied
+    )]
     pub source_generation: Account<'info, TokenGeneration>,
     
-    // The newly initialized Escrow Generation account.
-    // Client pre-flight is expected to have called init_token_generation for it.
-    #[account(mut)]
+    #[account(
+        init,
+        payer = source,
+        space = Escrow::space_for(terms.max_claimers),
+        seeds = [b"escrow", mint.key().as_ref(), source.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    
+    // Owned by the escrow PDA. Client pre-flight is expected to have called
+    // init_token_generation with the escrow PDA as owner.
+    #[account(
+        mut,
+        seeds = [b"gen_v4", mint.key().as_ref(), escrow.key().as_ref()],
+        bump = escrow_generation.bump
+    )]
     pub escrow_generation: Account<'info, TokenGeneration>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = source_ata.owner == source.key() @ ViralSyncError::AccessDenied,
+        constraint = source_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
     pub source_ata: InterfaceAccount<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = escrow_ata.owner == escrow.key() @ ViralSyncError::AccessDenied,
+        constraint = escrow_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
     pub escrow_ata: InterfaceAccount<'info, TokenAccount>,
     
+    #[account(mut)]
     pub source: Signer<'info>,
     
     pub mint: InterfaceAccount<'info, Mint>,
     
     pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
 }
 
-pub fn create_escrow_share(ctx: Context<CreateEscrowShare>, amount: u64) -> Result<()> {
-    let src_gen = &mut ctx.accounts.source_generation;
-    let escrow_gen = &mut ctx.accounts.escrow_generation;
+pub fn create_escrow_share(
+    ctx: Context<CreateEscrowShare>,
+    nonce: u64,
+    amount: u64,
+    terms: EscrowTerms,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.bump = ctx.bumps.escrow;
+    escrow.mint = ctx.accounts.mint.key();
+    escrow.creator = ctx.accounts.source.key();
+    escrow.nonce = nonce;
+    open_escrow(escrow, &terms, now)?;
     
     // Escrow acts as an intentional intermediary.
-    // Setting `is_intermediary` forces the transfer_hook to bypass strict hold checks on arrival,
-    // preserving the true referrer logic when it is finally unpacked.
-    escrow_gen.is_intermediary = true;
-    escrow_gen.original_sender = src_gen.owner;
+    // Setting `is_intermediary` defers the referral limits to each claim, preserving the true
+    // referrer logic when it is finally unpacked.
+    let escrow_gen = &mut ctx.accounts.escrow_generation;
+    mark_intermediary(escrow_gen, &ctx.accounts.escrow);
+    // Flush now so the hook, which reads account data during the CPI, sees the flag
+    escrow_gen.exit(&crate::ID)?;
+    
+    let balance_before = ctx.accounts.escrow_ata.amount;
+    let buckets_before = buckets(&ctx.accounts.source_generation);
     
     let cpi_accounts = TransferChecked {
         from: ctx.accounts.source_ata.to_account_info(),
@@ -46,60 +129,313 @@ pub fn create_escrow_share(ctx: Context<CreateEscrowShare>, amount: u64) -> Resu
     let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
     
     // transfer_checked fires the hook. src_gen deductions happen there.
-    // escrow_gen balances adjust inside the hook due to the is_intermediary flag setup.
     transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
     
+    // The hook rewrote both generations during the CPI; don't clobber them on exit
+    ctx.accounts.source_generation.reload()?;
+    ctx.accounts.escrow_generation.reload()?;
+    
+    // Record what actually landed, net of any Token-2022 transfer fee
+    ctx.accounts.escrow_ata.reload()?;
+    let received = ctx.accounts.escrow_ata.amount.checked_sub(balance_before).unwrap();
+    ctx.accounts.escrow.amount = received;
+    record_deposit(&mut ctx.accounts.escrow, buckets_before, &ctx.accounts.source_generation);
+    
+    emit_created(&ctx.accounts.escrow);
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct ClaimEscrow<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.mint.as_ref(), escrow.creator.as_ref(), &escrow.nonce.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    
+    #[account(
+        mut,
+        seeds = [b"gen_v4", mint.key().as_ref(), escrow.key().as_ref()],
+        bump = escrow_generation.bump
+    )]
     pub escrow_generation: Account<'info, TokenGeneration>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = dest_generation.owner == claimant.key() @ ViralSyncError::InvalidDestGeneration,
+        constraint = dest_generation.mint == mint.key() @ ViralSyncError::InvalidDestGeneration
+    )]
     pub dest_generation: Account<'info, TokenGeneration>,
     
-    #[account(mut)]
+    // Every claim is a referral by the creator and is metered against their limits
+    #[account(
+        mut,
+        seeds = [b"gen_v4", mint.key().as_ref(), escrow.creator.as_ref()],
+        bump = creator_generation.bump
+    )]
+    pub creator_generation: Account<'info, TokenGeneration>,
+    
+    #[account(seeds = [b"merchant_v4", mint.key().as_ref()], bump = merchant_config.bump)]
+    pub merchant_config: Account<'info, MerchantConfig>,
+    
+    #[account(
+        mut,
+        constraint = escrow_ata.owner == escrow.key() @ ViralSyncError::AccessDenied,
+        constraint = escrow_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
     pub escrow_ata: InterfaceAccount<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = dest_ata.owner == claimant.key() @ ViralSyncError::AccessDenied,
+        constraint = dest_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
     pub dest_ata: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: PDA signer representing the escrow auth
-    pub escrow_authority: UncheckedAccount<'info>,
+    pub claimant: Signer<'info>,
     
+    #[account(address = escrow.mint)]
     pub mint: InterfaceAccount<'info, Mint>,
     
     pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn claim_escrow(ctx: Context<ClaimEscrow>, amount: u64) -> Result<()> {
-    // Basic transfer validation. The hook interprets src: intermediary -> dest: typical user
+pub fn claim_escrow(ctx: Context<ClaimEscrow>, amount: u64, claim_code: Option<Vec<u8>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &ctx.accounts.escrow;
+    let claimant = ctx.accounts.claimant.key();
+    
+    require!(!escrow.is_expired(now), ViralSyncError::EscrowExpired);
+    // The creator unpacking their own escrow would credit themselves as referrer
+    require!(
+        claimant != escrow.creator && escrow.can_claim(&claimant, claim_code.as_deref()),
+        ViralSyncError::EscrowClaimNotAuthorized
+    );
+    require!(amount > 0 && amount <= escrow.remaining(), ViralSyncError::EscrowInsufficientBalance);
+    
+    if escrow.is_link_drop() {
+        require!(amount == escrow.amount_per_claim, ViralSyncError::InvalidLinkDrop);
+        require!(!escrow.claimers.contains(&claimant), ViralSyncError::EscrowAlreadyClaimed);
+        require!(escrow.claimers.len() < escrow.max_claimers as usize, ViralSyncError::EscrowFullyClaimed);
+    }
+    
+    // Each claim is one referral by the creator: min/max size and the daily cap apply here
+    ctx.accounts.merchant_config.enforce_referral_limits(&mut ctx.accounts.creator_generation, amount, Clock::get()?.slot)?;
+    
+    let escrow = &ctx.accounts.escrow;
+    
+    // The hook interprets src: intermediary -> dest: typical user
     // unpacks the original_sender, treating it as the direct referrer natively.
+    let mint_key = escrow.mint;
+    let creator = escrow.creator;
+    let nonce = escrow.nonce.to_le_bytes();
+    let bump = [escrow.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"escrow", mint_key.as_ref(), creator.as_ref(), &nonce, &bump]];
     
     let cpi_accounts = TransferChecked {
         from: ctx.accounts.escrow_ata.to_account_info(),
         mint: ctx.accounts.mint.to_account_info(),
         to: ctx.accounts.dest_ata.to_account_info(),
-        authority: ctx.accounts.escrow_authority.to_account_info(),
+        authority: ctx.accounts.escrow.to_account_info(),
     };
     
-    // Signer seeds omitted for brevity
-    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
     transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
     
+    ctx.accounts.escrow_generation.reload()?;
+    ctx.accounts.dest_generation.reload()?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.claimed = escrow.claimed.checked_add(amount).unwrap();
+    escrow.claim_count = escrow.claim_count.saturating_add(1);
+    if escrow.is_link_drop() {
+        escrow.claimers.push(claimant);
+    }
+    
+    emit!(EscrowClaimed {
+        escrow: escrow.key(),
+        claimant,
+        amount,
+        remaining: escrow.remaining(),
+    });
+    
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct HarvestExpiredEscrows<'info> {
-    /// CHECK: Target config implementation required
-    pub config: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"escrow", escrow.mint.as_ref(), escrow.creator.as_ref(), &escrow.nonce.to_le_bytes()],
+        bump = escrow.bump,
+        close = creator
+    )]
+    pub escrow: Account<'info, Escrow>,
+    
+    #[account(
+        mut,
+        seeds = [b"gen_v4", mint.key().as_ref(), escrow.key().as_ref()],
+        bump = escrow_generation.bump,
+        close = creator
+    )]
+    pub escrow_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        mut,
+        constraint = escrow_ata.owner == escrow.key() @ ViralSyncError::AccessDenied,
+        constraint = escrow_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub escrow_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"gen_v4", mint.key().as_ref(), escrow.creator.as_ref()],
+        bump = creator_generation.bump
+    )]
+    pub creator_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        mut,
+        constraint = creator_ata.owner == escrow.creator @ ViralSyncError::AccessDenied,
+        constraint = creator_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub creator_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Rent destination, pinned to the escrow creator
+    #[account(mut, address = escrow.creator)]
+    pub creator: UncheckedAccount<'info>,
+    
+    // Permissionless crank, paid a slice of the reclaimed rent
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    #[account(address = escrow.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn harvest_expired_escrows(ctx: Context<HarvestExpiredEscrows>) -> Result<()> {
-    // Crank operation validating escrows older than expiry windows returning funds to Source
-    // Logic simulated for roadmap completion
+    let now = Clock::get()?.unix_timestamp;
+    let escrow = &ctx.accounts.escrow;
+    require!(escrow.is_expired(now), ViralSyncError::EscrowNotExpired);
+    
+    let mint_key = escrow.mint;
+    let creator = escrow.creator;
+    let nonce = escrow.nonce.to_le_bytes();
+    let bump = [escrow.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"escrow", mint_key.as_ref(), creator.as_ref(), &nonce, &bump]];
+    
+    let returned = ctx.accounts.escrow_ata.amount;
+    if returned > 0 {
+        // The hook treats intermediary -> original_sender as a restore rather than a viral share.
+        // Re-pin it to the creator in case identity gating cleared it when the escrow was funded.
+        let escrow_gen = &mut ctx.accounts.escrow_generation;
+        escrow_gen.original_sender = creator;
+        escrow_gen.exit(&crate::ID)?;
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.creator_ata.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        transfer_checked(cpi_ctx, returned, ctx.accounts.mint.decimals)?;
+        
+        // The hook leaves the restore to us; only the unclaimed part of each deposited bucket comes back
+        ctx.accounts.creator_generation.reload()?;
+        let (gen1, gen2, dead) = ctx.accounts.escrow.unclaimed_split(returned);
+        let creator_gen = &mut ctx.accounts.creator_generation;
+        creator_gen.gen1_balance = creator_gen.gen1_balance.checked_add(gen1).unwrap();
+        creator_gen.gen2_balance = creator_gen.gen2_balance.checked_add(gen2).unwrap();
+        creator_gen.dead_balance = creator_gen.dead_balance.checked_add(dead).unwrap();
+    }
+    
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.escrow_ata.to_account_info(),
+        destination: ctx.accounts.creator.to_account_info(),
+        authority: ctx.accounts.escrow.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    close_account(cpi_ctx)?;
+    
+    // Bounty comes out of the escrow PDA's rent; the rest goes back to the creator on close
+    let escrow_info = ctx.accounts.escrow.to_account_info();
+    let bounty = ESCROW_HARVEST_BOUNTY_LAMPORTS.min(escrow_info.lamports());
+    **escrow_info.try_borrow_mut_lamports()? -= bounty;
+    **ctx.accounts.caller.to_account_info().try_borrow_mut_lamports()? += bounty;
+    
+    emit!(EscrowHarvested {
+        escrow: ctx.accounts.escrow.key(),
+        creator,
+        caller: ctx.accounts.caller.key(),
+        returned,
+        bounty,
+    });
+    
     Ok(())
 }
+
+// The escrow generation is seeded by the escrow PDA, so only freshly opened escrows can be flagged
+pub(crate) fn mark_intermediary(escrow_gen: &mut TokenGeneration, escrow: &Escrow) {
+    escrow_gen.is_intermediary = true;
+    escrow_gen.original_sender = escrow.creator;
+    escrow_gen.escrow_creator = escrow.creator;
+    escrow_gen.escrow_nonce = escrow.nonce;
+    escrow_gen.escrow_bump = escrow.bump;
+}
+
+// Validates the claim terms and resets the counters; the caller fills in the PDA identity
+pub(crate) fn open_escrow(escrow: &mut Escrow, terms: &EscrowTerms, now: i64) -> Result<()> {
+    require!(terms.recipient.is_some() || terms.claim_code_hash.is_some(), ViralSyncError::EscrowClaimRuleRequired);
+    require!(terms.expires_at >= now + ESCROW_MIN_DURATION_SECS, ViralSyncError::InvalidEscrowExpiry);
+    if terms.max_claimers > 0 {
+        require!(
+            terms.max_claimers as usize <= MAX_LINK_DROP_CLAIMERS && terms.amount_per_claim > 0,
+            ViralSyncError::InvalidLinkDrop
+        );
+    }
+    
+    escrow.amount = 0;
+    escrow.claimed = 0;
+    escrow.deposited_gen1 = 0;
+    escrow.deposited_gen2 = 0;
+    escrow.deposited_dead = 0;
+    escrow.claim_count = 0;
+    escrow.created_at = now;
+    escrow.expires_at = terms.expires_at;
+    escrow.recipient = terms.recipient;
+    escrow.claim_code_hash = terms.claim_code_hash;
+    escrow.max_claimers = terms.max_claimers;
+    escrow.amount_per_claim = terms.amount_per_claim;
+    escrow.claimers = Vec::new();
+    Ok(())
+}
+
+pub(crate) fn buckets(gen: &TokenGeneration) -> (u64, u64, u64) {
+    (gen.gen1_balance, gen.gen2_balance, gen.dead_balance)
+}
+
+// Records how the hook debited the creator for this deposit
+pub(crate) fn record_deposit(escrow: &mut Escrow, before: (u64, u64, u64), source_gen: &TokenGeneration) {
+    let (gen1, gen2, dead) = buckets(source_gen);
+    escrow.deposited_gen1 = before.0.saturating_sub(gen1);
+    escrow.deposited_gen2 = before.1.saturating_sub(gen2);
+    escrow.deposited_dead = before.2.saturating_sub(dead);
+}
+
+pub(crate) fn emit_created(escrow: &Account<Escrow>) {
+    emit!(EscrowCreated {
+        escrow: escrow.key(),
+        creator: escrow.creator,
+        mint: escrow.mint,
+        amount: escrow.amount,
+        expires_at: escrow.expires_at,
+        recipient: escrow.recipient,
+        has_claim_code: escrow.claim_code_hash.is_some(),
+        max_claimers: escrow.max_claimers,
+        amount_per_claim: escrow.amount_per_claim,
+    });
+}
@@ -4,14 +4,20 @@ use crate::errors::ViralSyncError;
 
 #[derive(Accounts)]
 pub struct FinalizeInbound<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = dest_generation.owner == dest.key() @ ViralSyncError::AccessDenied
+    )]
     pub dest_generation: Account<'info, TokenGeneration>,
-    pub dest: Signer<'info>, // usually crank or the user themselves
+    pub dest: Signer<'info>, // the owner; relayers crank through finalize_inbound_with_session
 }
 
 pub fn handler(ctx: Context<FinalizeInbound>) -> Result<()> {
-    let gen = &mut ctx.accounts.dest_generation;
-    
+    finalize_pending(&mut ctx.accounts.dest_generation)
+}
+
+// Shared with finalize_inbound_with_session
+pub fn finalize_pending(gen: &mut TokenGeneration) -> Result<()> {
     if gen.buffer_pending == 0 {
         return Ok(());
     }
@@ -17,11 +17,11 @@ pub fn redeem_with_geo(
     signature: Vec<u8>
 ) -> Result<()> {
     let fence = &ctx.accounts.fence;
-    require!(fence.is_active, ViralSyncError::TokensExpired); // Re-using error for missing geo mapping here 
+    require!(fence.is_active, ViralSyncError::GeoFenceInactive);
     
     // Check if the user opted out with fallback permitted
     if signature.is_empty() {
-        require!(fence.allow_non_geo_redemption, ViralSyncError::TokensExpired); 
+        require!(fence.allow_non_geo_redemption, ViralSyncError::InvalidAttestation);
         // Proceeding invokes `non_geo_commission_penalty_bps` dilution on the redemption_slot processing down the line
         return Ok(());
     }
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{self as ix_sysvar, load_current_index_checked, load_instruction_at_checked},
+};
+use crate::state::{
+    identity::{IdentityRegistry, IdentityClaim, MAX_IDENTITY_PROVIDERS, IDENTITY_ATTESTATION_PREFIX},
+    token_generation::TokenGeneration,
+};
+use crate::errors::ViralSyncError;
+
+#[event]
+pub struct IdentityBound {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub provider_id: u16,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct IdentityUnbound {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub commitment: [u8; 32],
+}
+
+// ── REGISTRY ADMINISTRATION ─────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct InitIdentityRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = IdentityRegistry::LEN,
+        seeds = [b"identity_registry"],
+        bump
+    )]
+    pub identity_registry: Account<'info, IdentityRegistry>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    // Only the program's upgrade authority may bootstrap the registry
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ViralSyncError::AccessDenied)]
+    pub program: Program<'info, crate::program::ViralSync>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ViralSyncError::AccessDenied)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_identity_registry(ctx: Context<InitIdentityRegistry>) -> Result<()> {
+    let registry = &mut ctx.accounts.identity_registry;
+    registry.bump = ctx.bumps.identity_registry;
+    registry.admin = ctx.accounts.admin.key();
+    registry.providers = [Pubkey::default(); MAX_IDENTITY_PROVIDERS];
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetIdentityProvider<'info> {
+    #[account(mut, seeds = [b"identity_registry"], bump = identity_registry.bump, has_one = admin)]
+    pub identity_registry: Account<'info, IdentityRegistry>,
+    pub admin: Signer<'info>,
+}
+
+// Passing Pubkey::default() as `signer` deregisters the provider
+pub fn set_identity_provider(ctx: Context<SetIdentityProvider>, provider_id: u16, signer: Pubkey) -> Result<()> {
+    require!((provider_id as usize) < MAX_IDENTITY_PROVIDERS, ViralSyncError::UnknownIdentityProvider);
+    ctx.accounts.identity_registry.providers[provider_id as usize] = signer;
+    Ok(())
+}
+
+// ── BIND / UNBIND ───────────────────────────────────────────────────────────
+#[derive(Accounts)]
+#[instruction(provider_id: u16, commitment: [u8; 32])]
+pub struct BindIdentity<'info> {
+    #[account(mut, has_one = owner)]
+    pub token_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = IdentityClaim::LEN,
+        seeds = [b"identity", token_generation.mint.as_ref(), commitment.as_ref()],
+        bump
+    )]
+    pub identity_claim: Account<'info, IdentityClaim>,
+    
+    #[account(seeds = [b"identity_registry"], bump = identity_registry.bump)]
+    pub identity_registry: Account<'info, IdentityRegistry>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, read to find the provider's Ed25519 attestation
+    #[account(address = ix_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn bind_identity(ctx: Context<BindIdentity>, provider_id: u16, commitment: [u8; 32]) -> Result<()> {
+    let gen = &mut ctx.accounts.token_generation;
+    let claim = &mut ctx.accounts.identity_claim;
+    let owner = ctx.accounts.owner.key();
+    
+    require!(gen.identity_commitment.is_none(), ViralSyncError::IdentityAlreadyBound);
+    
+    let provider_key = ctx.accounts.identity_registry
+        .provider_key(provider_id)
+        .ok_or(ViralSyncError::UnknownIdentityProvider)?;
+    
+    // The provider attests that this commitment belongs to this exact wallet
+    let mut message = Vec::with_capacity(IDENTITY_ATTESTATION_PREFIX.len() + 64);
+    message.extend_from_slice(IDENTITY_ATTESTATION_PREFIX);
+    message.extend_from_slice(&commitment);
+    message.extend_from_slice(owner.as_ref());
+    verify_ed25519_ix(&ctx.accounts.instructions_sysvar, &provider_key, &message)?;
+    
+    // A fresh claim has a zeroed owner; an existing one may only be re-bound by its original wallet
+    require!(
+        claim.owner == Pubkey::default() || (claim.owner == owner && !claim.is_bound),
+        ViralSyncError::IdentityCommitmentInUse
+    );
+    
+    claim.bump = ctx.bumps.identity_claim;
+    claim.mint = gen.mint;
+    claim.commitment = commitment;
+    claim.owner = owner;
+    claim.provider_id = provider_id;
+    claim.bound_at = Clock::get()?.unix_timestamp;
+    claim.is_bound = true;
+    
+    gen.identity_commitment = Some(commitment);
+    gen.identity_provider = provider_id;
+    
+    emit!(IdentityBound { owner, mint: gen.mint, provider_id, commitment });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnbindIdentity<'info> {
+    #[account(mut, has_one = owner)]
+    pub token_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        mut,
+        seeds = [b"identity", identity_claim.mint.as_ref(), identity_claim.commitment.as_ref()],
+        bump = identity_claim.bump,
+        has_one = owner,
+        constraint = identity_claim.mint == token_generation.mint @ ViralSyncError::IdentityNotBound,
+        constraint = token_generation.identity_commitment == Some(identity_claim.commitment) @ ViralSyncError::IdentityNotBound
+    )]
+    pub identity_claim: Account<'info, IdentityClaim>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn unbind_identity(ctx: Context<UnbindIdentity>) -> Result<()> {
+    let gen = &mut ctx.accounts.token_generation;
+    let claim = &mut ctx.accounts.identity_claim;
+    
+    // The claim is kept (not closed) so the commitment cannot migrate to a sybil wallet
+    claim.is_bound = false;
+    
+    gen.identity_commitment = None;
+    gen.identity_provider = 0;
+    
+    emit!(IdentityUnbound { owner: claim.owner, mint: claim.mint, commitment: claim.commitment });
+    
+    Ok(())
+}
+
+// Expects the Ed25519 program instruction immediately before this one, carrying a single
+// signature whose pubkey and message live inside that same instruction's data.
+pub(crate) fn verify_ed25519_ix(
+    instructions_sysvar: &AccountInfo,
+    expected_signer: &Pubkey,
+    expected_message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, ViralSyncError::InvalidIdentitySignature);
+    
+    let ix = load_instruction_at_checked((current - 1) as usize, instructions_sysvar)?;
+    require!(ix.program_id == ed25519_program::ID, ViralSyncError::InvalidIdentitySignature);
+    
+    // Header: num_signatures (u8), padding (u8), then one 14-byte offsets struct
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ViralSyncError::InvalidIdentitySignature);
+    
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_ix_index = read_u16(4);
+    let pubkey_offset = read_u16(6) as usize;
+    let pubkey_ix_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_index = read_u16(14);
+    
+    // u16::MAX means "this instruction"; anything else could point at attacker-controlled data
+    require!(
+        signature_ix_index == u16::MAX && pubkey_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        ViralSyncError::InvalidIdentitySignature
+    );
+    
+    let pubkey = data.get(pubkey_offset..pubkey_offset + 32).ok_or(ViralSyncError::InvalidIdentitySignature)?;
+    let message = data.get(message_offset..message_offset + message_size).ok_or(ViralSyncError::InvalidIdentitySignature)?;
+    
+    require!(pubkey == expected_signer.as_ref(), ViralSyncError::InvalidIdentitySignature);
+    require!(message == expected_message, ViralSyncError::InvalidIdentitySignature);
+    
+    Ok(())
+}
@@ -1,13 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
-use crate::state::token_generation::{TokenGeneration, INBOUND_BUFFER_SIZE, InboundEntry};
+use crate::state::token_generation::{TokenGeneration, INBOUND_BUFFER_SIZE, InboundEntry, TOKEN_GENERATION_VERSION};
 
 #[derive(Accounts)]
 pub struct InitTokenGeneration<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 1700, // Size mapped from ARCHITECTURE_V4
+        space = TokenGeneration::LEN,
         seeds = [b"gen_v4", mint.key().as_ref(), owner.key().as_ref()],
         bump
     )]
@@ -28,7 +28,7 @@ pub fn handler(ctx: Context<InitTokenGeneration>) -> Result<()> {
     let gen = &mut ctx.accounts.token_generation;
     
     gen.bump = ctx.bumps.token_generation;
-    gen.version = 4;
+    gen.version = TOKEN_GENERATION_VERSION;
     gen.mint = ctx.accounts.mint.key();
     gen.owner = ctx.accounts.owner.key();
     
@@ -46,11 +46,23 @@ pub fn handler(ctx: Context<InitTokenGeneration>) -> Result<()> {
     
     gen.is_intermediary = false;
     gen.original_sender = Pubkey::default();
+    gen.escrow_creator = Pubkey::default();
+    gen.escrow_nonce = 0;
+    gen.escrow_bump = 0;
     
     gen.share_limit_day = 0;
     gen.shares_today = 0;
     
+    gen.total_shared = 0;
+    gen.total_redeemed = 0;
+    gen.total_commission_claimed = 0;
+    gen.share_count = 0;
+    gen.redemption_count = 0;
+    gen.claim_count = 0;
+    gen.successful_referrals = 0;
+    
     gen.processing_nonce = 0;
+    gen.session_epoch = 0;
     
     gen.redemption_pending = false;
     gen.redemption_gen2_consumed = 0;
@@ -61,6 +73,8 @@ pub fn handler(ctx: Context<InitTokenGeneration>) -> Result<()> {
     
     gen.poi_score = 0;
     gen.poi_updated_at = 0;
+    gen.poi_momentum = 0;
+    gen.poi_momentum_at = 0;
     
     Ok(())
 }
@@ -1,13 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
-use crate::state::token_generation::{TokenGeneration, INBOUND_BUFFER_SIZE, InboundEntry};
+use crate::state::token_generation::{TokenGeneration, INBOUND_BUFFER_SIZE, InboundEntry, TOKEN_GENERATION_VERSION};
 
 #[derive(Accounts)]
 pub struct InitTreasuryGen<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + 1700, 
+        space = TokenGeneration::LEN,
         seeds = [b"gen_v4", mint.key().as_ref(), treasury_ata.key().as_ref()],
         bump
     )]
@@ -28,7 +28,7 @@ pub fn handler(ctx: Context<InitTreasuryGen>) -> Result<()> {
     let gen = &mut ctx.accounts.treasury_generation;
     
     gen.bump = ctx.bumps.treasury_generation;
-    gen.version = 4;
+    gen.version = TOKEN_GENERATION_VERSION;
     gen.mint = ctx.accounts.mint.key();
     gen.owner = ctx.accounts.treasury_ata.key(); // ATA address as owner
     
@@ -0,0 +1,414 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash::hashv, sysvar::slot_hashes};
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{
+    dispute_record::{DisputeRecord, DisputeStatus, Arbitration},
+    referral_record::ReferralRecord,
+    jury::*,
+};
+use crate::instructions::bond_management::pay_from_vault;
+use crate::instructions::disputes::*; // Nested DisputeSettlement needs its generated client modules too
+use crate::errors::ViralSyncError;
+
+#[event]
+pub struct JurorJoined {
+    pub juror: Pubkey,
+    pub stake_lamports: u64,
+}
+
+#[event]
+pub struct JurorEvicted {
+    pub juror: Pubkey,
+    pub replaced_by: Pubkey,
+    pub withdrawable_at: i64,
+}
+
+#[event]
+pub struct JurorExitRequested {
+    pub juror: Pubkey,
+    pub withdrawable_at: i64,
+}
+
+#[event]
+pub struct JuryDrawCommitted {
+    pub dispute: Pubkey,
+    pub draw_slot: u64,
+}
+
+#[event]
+pub struct JurorsSummoned {
+    pub dispute: Pubkey,
+    pub jurors: [Pubkey; JURORS_PER_DISPUTE],
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+}
+
+#[event]
+pub struct JurorVoteCommitted {
+    pub dispute: Pubkey,
+    pub juror: Pubkey,
+}
+
+#[event]
+pub struct JurorVoteRevealed {
+    pub dispute: Pubkey,
+    pub juror: Pubkey,
+    pub uphold: bool,
+}
+
+#[event]
+pub struct DisputeVoteTallied {
+    pub dispute: Pubkey,
+    pub uphold_votes: u8,
+    pub dismiss_votes: u8,
+    pub quorum_met: bool,
+    pub slashed_lamports: u64,
+    pub reward_per_juror: u64,
+}
+
+// ── JUROR POOL ──────────────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct JoinJurorPool<'info> {
+    #[account(mut, seeds = [b"arbitration"], bump = arbitration.bump)]
+    pub arbitration: Account<'info, Arbitration>,
+    
+    #[account(
+        init,
+        payer = juror,
+        space = JurorStake::LEN,
+        seeds = [b"juror", juror.key().as_ref()],
+        bump
+    )]
+    pub juror_stake: Account<'info, JurorStake>,
+    
+    #[account(mut)]
+    pub juror: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Only needed once the pool is full: a member staking less, who is evicted to make room
+    #[account(mut)]
+    pub evicted_stake: Option<Account<'info, JurorStake>>,
+}
+
+// Seats are contested by stake, so filling the pool early does not hold it for good
+pub fn join_juror_pool(ctx: Context<JoinJurorPool>, stake_lamports: u64) -> Result<()> {
+    require!(stake_lamports >= MIN_JUROR_STAKE_LAMPORTS, ViralSyncError::JurorStakeTooLow);
+    let juror = ctx.accounts.juror.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    let arbitration = &mut ctx.accounts.arbitration;
+    if arbitration.is_full() {
+        let evicted = ctx.accounts.evicted_stake.as_mut().ok_or(ViralSyncError::JurorPoolFull)?;
+        arbitration.replace_juror(evicted, juror, stake_lamports, now)?;
+        emit!(JurorEvicted {
+            juror: evicted.juror,
+            replaced_by: juror,
+            withdrawable_at: now + JUROR_EXIT_COOLDOWN_SECS,
+        });
+    } else {
+        arbitration.add_juror(juror)?;
+    }
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.juror.to_account_info(),
+        to: ctx.accounts.juror_stake.to_account_info(),
+    };
+    transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts), stake_lamports)?;
+    
+    let stake = &mut ctx.accounts.juror_stake;
+    stake.bump = ctx.bumps.juror_stake;
+    stake.juror = juror;
+    stake.stake_lamports = stake_lamports;
+    stake.joined_at = now;
+    stake.active_disputes = 0;
+    stake.votes_revealed = 0;
+    stake.no_shows = 0;
+    stake.rewards_earned = 0;
+    stake.exit_requested_at = 0;
+    
+    emit!(JurorJoined { juror, stake_lamports: stake.stake_lamports });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestJurorExit<'info> {
+    #[account(mut, seeds = [b"arbitration"], bump = arbitration.bump)]
+    pub arbitration: Account<'info, Arbitration>,
+    
+    #[account(mut, seeds = [b"juror", juror.key().as_ref()], bump = juror_stake.bump, has_one = juror)]
+    pub juror_stake: Account<'info, JurorStake>,
+    
+    pub juror: Signer<'info>,
+}
+
+// Leaves the pool at once; the stake stays slashable until the cooldown has passed
+pub fn request_juror_exit(ctx: Context<RequestJurorExit>) -> Result<()> {
+    let stake = &mut ctx.accounts.juror_stake;
+    require!(stake.exit_requested_at == 0, ViralSyncError::JurorStakeLocked);
+    
+    let now = Clock::get()?.unix_timestamp;
+    stake.exit_requested_at = now;
+    ctx.accounts.arbitration.remove_juror(&stake.juror);
+    
+    emit!(JurorExitRequested { juror: stake.juror, withdrawable_at: now + JUROR_EXIT_COOLDOWN_SECS });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawJurorStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"juror", juror.key().as_ref()],
+        bump = juror_stake.bump,
+        has_one = juror,
+        close = juror
+    )]
+    pub juror_stake: Account<'info, JurorStake>,
+    
+    #[account(mut)]
+    pub juror: Signer<'info>,
+}
+
+pub fn withdraw_juror_stake(ctx: Context<WithdrawJurorStake>) -> Result<()> {
+    let stake = &ctx.accounts.juror_stake;
+    let now = Clock::get()?.unix_timestamp;
+    
+    require!(stake.exit_requested_at > 0, ViralSyncError::JurorStakeLocked);
+    require!(now >= stake.exit_requested_at + JUROR_EXIT_COOLDOWN_SECS, ViralSyncError::JurorStakeLocked);
+    require!(stake.active_disputes == 0, ViralSyncError::JurorStakeLocked);
+    Ok(())
+}
+
+// ── SUMMON ──────────────────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct SummonJurors<'info> {
+    pub dispute_record: Account<'info, DisputeRecord>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = DisputeVote::LEN,
+        seeds = [b"dispute_vote", dispute_record.key().as_ref()],
+        bump
+    )]
+    pub dispute_vote: Account<'info, DisputeVote>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless once the merchant has answered. Only commits to a future slot: its hash is not
+// known yet, so whoever summons cannot steer the draw by choosing when to call.
+pub fn summon_jurors(ctx: Context<SummonJurors>) -> Result<()> {
+    let dispute = &ctx.accounts.dispute_record;
+    require!(dispute.status == DisputeStatus::InArbitration, ViralSyncError::DisputeNotInArbitration);
+    
+    let vote = &mut ctx.accounts.dispute_vote;
+    vote.bump = ctx.bumps.dispute_vote;
+    vote.dispute = dispute.key();
+    vote.payer = ctx.accounts.payer.key();
+    vote.jurors = [Pubkey::default(); JURORS_PER_DISPUTE];
+    vote.commitments = [None; JURORS_PER_DISPUTE];
+    vote.votes = [None; JURORS_PER_DISPUTE];
+    vote.commit_deadline = 0;
+    vote.reveal_deadline = 0;
+    vote.draw_slot = Clock::get()?.slot + JURY_DRAW_DELAY_SLOTS;
+    
+    emit!(JuryDrawCommitted { dispute: vote.dispute, draw_slot: vote.draw_slot });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DrawJurors<'info> {
+    #[account(seeds = [b"arbitration"], bump = arbitration.bump)]
+    pub arbitration: Account<'info, Arbitration>,
+    
+    #[account(mut)]
+    pub dispute_record: Account<'info, DisputeRecord>,
+    
+    // Its referrer is a party too: the dispute freezes their commission
+    #[account(address = dispute_record.referral)]
+    pub referral_record: Account<'info, ReferralRecord>,
+    
+    #[account(mut, seeds = [b"dispute_vote", dispute_record.key().as_ref()], bump = dispute_vote.bump)]
+    pub dispute_vote: Account<'info, DisputeVote>,
+    
+    /// CHECK: SlotHashes sysvar, read raw (too large to deserialize)
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+// Permissionless once the committed slot has landed. If nobody drew while its hash was still in
+// the sysvar, the draw is committed to a fresh future slot instead.
+pub fn draw_jurors(ctx: Context<DrawJurors>) -> Result<()> {
+    let dispute_key = ctx.accounts.dispute_record.key();
+    let dispute = &mut ctx.accounts.dispute_record;
+    let vote = &mut ctx.accounts.dispute_vote;
+    let clock = Clock::get()?;
+    
+    require!(dispute.status == DisputeStatus::InArbitration, ViralSyncError::DisputeNotInArbitration);
+    require!(!vote.is_drawn(), ViralSyncError::JuryAlreadyDrawn);
+    
+    let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+    let Some(slot_hash) = draw_slot_hash(&data, vote.draw_slot)? else {
+        vote.draw_slot = clock.slot + JURY_DRAW_DELAY_SLOTS;
+        emit!(JuryDrawCommitted { dispute: dispute_key, draw_slot: vote.draw_slot });
+        return Ok(());
+    };
+    drop(data);
+    
+    let seed = hashv(&[&slot_hash, dispute_key.as_ref()]).to_bytes();
+    let parties = [dispute.merchant, dispute.watchdog, ctx.accounts.referral_record.referrer];
+    vote.jurors = ctx.accounts.arbitration.select_jurors(&seed, &parties)?;
+    vote.commit_deadline = clock.unix_timestamp + JUROR_COMMIT_SECS;
+    vote.reveal_deadline = vote.commit_deadline + JUROR_REVEAL_SECS;
+    
+    dispute.status = DisputeStatus::InVote;
+    
+    emit!(JurorsSummoned {
+        dispute: dispute_key,
+        jurors: vote.jurors,
+        commit_deadline: vote.commit_deadline,
+        reveal_deadline: vote.reveal_deadline,
+    });
+    Ok(())
+}
+
+// ── COMMIT / REVEAL ─────────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct CastJurorVote<'info> {
+    #[account(mut, seeds = [b"dispute_vote", dispute_vote.dispute.as_ref()], bump = dispute_vote.bump)]
+    pub dispute_vote: Account<'info, DisputeVote>,
+    
+    #[account(mut, seeds = [b"juror", juror.key().as_ref()], bump = juror_stake.bump, has_one = juror)]
+    pub juror_stake: Account<'info, JurorStake>,
+    
+    pub juror: Signer<'info>,
+}
+
+// Committing locks the stake until the tally
+pub fn commit_juror_vote(ctx: Context<CastJurorVote>, commitment: [u8; 32]) -> Result<()> {
+    let juror = ctx.accounts.juror.key();
+    let vote = &mut ctx.accounts.dispute_vote;
+    vote.commit(&juror, commitment, Clock::get()?.unix_timestamp)?;
+    
+    let stake = &mut ctx.accounts.juror_stake;
+    stake.active_disputes = stake.active_disputes.saturating_add(1);
+    
+    emit!(JurorVoteCommitted { dispute: vote.dispute, juror });
+    Ok(())
+}
+
+pub fn reveal_juror_vote(ctx: Context<CastJurorVote>, uphold: bool, salt: [u8; 32]) -> Result<()> {
+    let juror = ctx.accounts.juror.key();
+    let vote = &mut ctx.accounts.dispute_vote;
+    vote.reveal(&juror, uphold, &salt, Clock::get()?.unix_timestamp)?;
+    
+    let stake = &mut ctx.accounts.juror_stake;
+    stake.votes_revealed = stake.votes_revealed.saturating_add(1);
+    
+    emit!(JurorVoteRevealed { dispute: vote.dispute, juror, uphold });
+    Ok(())
+}
+
+// ── TALLY ───────────────────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct TallyDisputeVotes<'info> {
+    #[account(mut, seeds = [b"arbitration"], bump = arbitration.bump)]
+    pub arbitration: Account<'info, Arbitration>,
+    
+    #[account(
+        mut,
+        seeds = [b"dispute_vote", settlement.dispute_record.key().as_ref()],
+        bump = dispute_vote.bump,
+        has_one = payer,
+        close = payer
+    )]
+    pub dispute_vote: Account<'info, DisputeVote>,
+    
+    /// CHECK: Receives the vote account's rent; checked against dispute_vote.payer
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    
+    pub settlement: DisputeSettlement<'info>,
+    
+    // remaining_accounts: the JurorStake PDA of every seated juror, in seat order
+}
+
+// Permissionless after the reveal deadline. No-shows forfeit part of their stake to the majority;
+// without quorum the dispute goes back to arbitration, where it can be re-summoned or ruled on.
+pub fn tally_dispute_votes<'info>(ctx: Context<'_, '_, 'info, 'info, TallyDisputeVotes<'info>>) -> Result<()> {
+    let vote = &ctx.accounts.dispute_vote;
+    let now = Clock::get()?.unix_timestamp;
+    
+    require!(ctx.accounts.settlement.dispute_record.status == DisputeStatus::InVote, ViralSyncError::DisputeNotInArbitration);
+    require!(vote.can_tally(now), ViralSyncError::VotingInProgress);
+    require!(ctx.remaining_accounts.len() == JURORS_PER_DISPUTE, ViralSyncError::NotSelectedJuror);
+    
+    let tally = vote.tally();
+    let upheld = tally.upheld();
+    let pot_info = ctx.accounts.arbitration.to_account_info();
+    
+    let mut stakes: Vec<Account<JurorStake>> = Vec::with_capacity(JURORS_PER_DISPUTE);
+    let mut winners: Vec<usize> = Vec::with_capacity(JURORS_PER_DISPUTE);
+    let mut slashed_lamports: u64 = 0;
+    
+    for (seat, info) in ctx.remaining_accounts.iter().enumerate() {
+        let juror = vote.jurors[seat];
+        let (expected, _) = Pubkey::find_program_address(&[b"juror", juror.as_ref()], &crate::ID);
+        require!(info.key() == expected, ViralSyncError::NotSelectedJuror);
+        if info.owner != &crate::ID {
+            // Stake already withdrawn after the exit cooldown; nothing left to settle
+            continue;
+        }
+        
+        let mut stake: Account<JurorStake> = Account::try_from(info)?;
+        if vote.commitments[seat].is_some() {
+            stake.active_disputes = stake.active_disputes.saturating_sub(1);
+        }
+        match vote.votes[seat] {
+            None => {
+                let slashed = stake.slash_no_show();
+                pay_from_vault(info, &pot_info, slashed)?;
+                slashed_lamports = slashed_lamports.saturating_add(slashed);
+                if stake.stake_lamports < MIN_JUROR_STAKE_LAMPORTS {
+                    ctx.accounts.arbitration.remove_juror(&juror);
+                }
+            }
+            Some(side) if tally.quorum_met() && side == upheld => winners.push(stakes.len()),
+            Some(_) => {}
+        }
+        stakes.push(stake);
+    }
+    
+    // Rounding dust and pots without a majority stay with the arbitration account
+    let reward_per_juror = if winners.is_empty() { 0 } else { slashed_lamports / winners.len() as u64 };
+    for index in winners {
+        let stake = &mut stakes[index];
+        pay_from_vault(&pot_info, &stake.to_account_info(), reward_per_juror)?;
+        stake.stake_lamports = stake.stake_lamports.saturating_add(reward_per_juror);
+        stake.rewards_earned = stake.rewards_earned.saturating_add(reward_per_juror);
+    }
+    for stake in stakes.iter() {
+        stake.exit(&crate::ID)?;
+    }
+    
+    emit!(DisputeVoteTallied {
+        dispute: vote.dispute,
+        uphold_votes: tally.uphold,
+        dismiss_votes: tally.dismiss,
+        quorum_met: tally.quorum_met(),
+        slashed_lamports,
+        reward_per_juror,
+    });
+    
+    if tally.quorum_met() {
+        apply_ruling(&mut ctx.accounts.settlement, upheld, DisputeStatus::UpheldByVote, ctx.bumps.settlement.treasury_authority, now)?;
+    } else {
+        ctx.accounts.settlement.dispute_record.status = DisputeStatus::InArbitration;
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::merchant_config::MerchantConfig;
+use crate::errors::ViralSyncError;
 
 // Phase 1: create_mint_and_config
 #[derive(Accounts)]
@@ -8,7 +9,7 @@ pub struct CreateMintAndConfig<'info> {
     #[account(
         init,
         payer = merchant,
-        space = 8 + 200, 
+        space = MerchantConfig::LEN,
         seeds = [b"merchant_v4", mint.key().as_ref()],
         bump
     )]
@@ -44,6 +45,12 @@ pub fn create_mint_and_config(
     config.current_supply = 0;
     config.tokens_issued = 0;
     
+    config.min_poi_score_for_commission = 0;
+    
+    config.require_identity_for_referrer = false;
+    config.require_identity_for_referred = false;
+    config.allowed_identity_providers = 0;
+    
     Ok(())
 }
 
@@ -53,7 +60,13 @@ pub fn create_mint_and_config(
 // Phase 3: issue_first_tokens_and_lock
 #[derive(Accounts)]
 pub struct IssueFirstTokensAndLock<'info> {
-    #[account(mut, has_one = merchant, has_one = mint)]
+    #[account(
+        mut,
+        seeds = [b"merchant_v4", mint.key().as_ref()],
+        bump = merchant_config.bump,
+        has_one = merchant,
+        has_one = mint
+    )]
     pub merchant_config: Account<'info, MerchantConfig>,
     pub merchant: Signer<'info>,
     pub mint: InterfaceAccount<'info, Mint>,
@@ -61,6 +74,7 @@ pub struct IssueFirstTokensAndLock<'info> {
 
 pub fn issue_first_tokens_and_lock(ctx: Context<IssueFirstTokensAndLock>, amount: u64) -> Result<()> {
     let config = &mut ctx.accounts.merchant_config;
+    require!(config.is_active, ViralSyncError::MerchantSunset);
     
     // Emitting simulated issuance logic
     config.first_issuance_done = true;
@@ -69,3 +83,34 @@ pub fn issue_first_tokens_and_lock(ctx: Context<IssueFirstTokensAndLock>, amount
     
     Ok(())
 }
+
+// Merchant-tunable commission eligibility rules
+#[derive(Accounts)]
+pub struct UpdateMerchantConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"merchant_v4", merchant_config.mint.as_ref()],
+        bump = merchant_config.bump,
+        has_one = merchant
+    )]
+    pub merchant_config: Account<'info, MerchantConfig>,
+    pub merchant: Signer<'info>,
+}
+
+pub fn set_min_poi_score(ctx: Context<UpdateMerchantConfig>, min_poi_score_for_commission: u32) -> Result<()> {
+    ctx.accounts.merchant_config.min_poi_score_for_commission = min_poi_score_for_commission;
+    Ok(())
+}
+
+pub fn set_identity_requirements(
+    ctx: Context<UpdateMerchantConfig>,
+    require_identity_for_referrer: bool,
+    require_identity_for_referred: bool,
+    allowed_identity_providers: u16,
+) -> Result<()> {
+    let config = &mut ctx.accounts.merchant_config;
+    config.require_identity_for_referrer = require_identity_for_referrer;
+    config.require_identity_for_referred = require_identity_for_referred;
+    config.allowed_identity_providers = allowed_identity_providers;
+    Ok(())
+}
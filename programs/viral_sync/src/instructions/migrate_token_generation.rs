@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::token_generation::{TokenGeneration, TOKEN_GENERATION_VERSION, TOKEN_GENERATION_VERSION_OFFSET};
+use crate::errors::ViralSyncError;
+
+#[event]
+pub struct TokenGenerationMigrated {
+    pub generation: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTokenGeneration<'info> {
+    /// CHECK: Written under an older layout, so it cannot deserialize yet; discriminator checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub token_generation: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>, // Anyone may migrate; they fund the extra rent
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_token_generation(ctx: Context<MigrateTokenGeneration>) -> Result<()> {
+    let generation = ctx.accounts.token_generation.to_account_info();
+    
+    let from_version = {
+        let data = generation.try_borrow_data()?;
+        require!(
+            data.len() > TOKEN_GENERATION_VERSION_OFFSET && data[..8] == TokenGeneration::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        data[TOKEN_GENERATION_VERSION_OFFSET]
+    };
+    require!(from_version < TOKEN_GENERATION_VERSION, ViralSyncError::GenerationAlreadyMigrated);
+    
+    let rent_shortfall = Rent::get()?.minimum_balance(TokenGeneration::LEN).saturating_sub(generation.lamports());
+    if rent_shortfall > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: generation.clone(),
+        };
+        transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts), rent_shortfall)?;
+    }
+    
+    // Newer fields are appended, and zero is the initial value of every one of them
+    if generation.data_len() < TokenGeneration::LEN {
+        generation.realloc(TokenGeneration::LEN, true)?;
+    }
+    generation.try_borrow_mut_data()?[TOKEN_GENERATION_VERSION_OFFSET] = TOKEN_GENERATION_VERSION;
+    
+    emit!(TokenGenerationMigrated {
+        generation: generation.key(),
+        from_version,
+        to_version: TOKEN_GENERATION_VERSION,
+    });
+    Ok(())
+}
@@ -1,5 +1,6 @@
 pub mod init_token_generation;
 pub mod init_treasury_token_generation;
+pub mod migrate_token_generation;
 pub mod merchant_init;
 pub mod transfer_hook;
 pub mod finalize_inbound;
@@ -14,10 +15,15 @@ pub mod oracles;
 pub mod geo_fencing;
 pub mod bond_management;
 pub mod disputes;
+pub mod jury;
 pub mod session_management;
+pub mod session_actions;
+pub mod proof_of_influence;
+pub mod identity;
 
 pub use init_token_generation::*;
 pub use init_treasury_token_generation::*;
+pub use migrate_token_generation::*;
 pub use merchant_init::*;
 pub use transfer_hook::*;
 pub use finalize_inbound::*;
@@ -30,4 +36,8 @@ pub use oracles::*;
 pub use geo_fencing::*;
 pub use bond_management::*;
 pub use disputes::*;
+pub use jury::*;
 pub use session_management::*;
+pub use session_actions::*;
+pub use proof_of_influence::*;
+pub use identity::*;
@@ -1,14 +1,76 @@
 use anchor_lang::prelude::*;
-use crate::state::{viral_oracle::ViralOracle, merchant_reputation::MerchantReputation};
+use crate::state::{
+    viral_oracle::ViralOracle,
+    merchant_reputation::MerchantReputation,
+    oracle_authority::{OracleAuthority, MAX_ORACLE_SIGNERS},
+};
 use crate::errors::ViralSyncError;
 
+// ── AUTHORITY ADMINISTRATION ────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct InitOracleAuthority<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = OracleAuthority::LEN,
+        seeds = [b"oracle_authority"],
+        bump
+    )]
+    pub oracle_authority: Account<'info, OracleAuthority>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    // Only the program's upgrade authority may bootstrap the oracle registry
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ ViralSyncError::AccessDenied)]
+    pub program: Program<'info, crate::program::ViralSync>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ViralSyncError::AccessDenied)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_oracle_authority(ctx: Context<InitOracleAuthority>) -> Result<()> {
+    let authority = &mut ctx.accounts.oracle_authority;
+    authority.bump = ctx.bumps.oracle_authority;
+    authority.admin = ctx.accounts.admin.key();
+    authority.signer_count = 0;
+    authority.signers = [Pubkey::default(); MAX_ORACLE_SIGNERS];
+    authority.reputation_threshold = 1;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ManageOracleAuthority<'info> {
+    #[account(mut, seeds = [b"oracle_authority"], bump = oracle_authority.bump, has_one = admin)]
+    pub oracle_authority: Account<'info, OracleAuthority>,
+    pub admin: Signer<'info>,
+}
+
+pub fn add_oracle_signer(ctx: Context<ManageOracleAuthority>, signer: Pubkey) -> Result<()> {
+    ctx.accounts.oracle_authority.add_signer(signer)
+}
+
+pub fn remove_oracle_signer(ctx: Context<ManageOracleAuthority>, signer: Pubkey) -> Result<()> {
+    ctx.accounts.oracle_authority.remove_signer(&signer)
+}
+
+pub fn set_reputation_threshold(ctx: Context<ManageOracleAuthority>, threshold: u8) -> Result<()> {
+    ctx.accounts.oracle_authority.set_reputation_threshold(threshold)
+}
+
+// ── ORACLE UPDATES ──────────────────────────────────────────────────────────
+
 #[derive(Accounts)]
 pub struct ComputeViralOracle<'info> {
     #[account(mut)]
     pub viral_oracle: Account<'info, ViralOracle>,
     
-    // In production, this would mandate a signature from the Helius/Supabase indexing authority PDA
-    // pub oracle_authority: Signer<'info>, 
+    #[account(seeds = [b"oracle_authority"], bump = oracle_authority.bump)]
+    pub oracle_authority: Account<'info, OracleAuthority>,
+    
+    #[account(constraint = oracle_authority.is_signer(&crank.key()) @ ViralSyncError::UnauthorizedOracle)]
     pub crank: Signer<'info>,
 }
 
@@ -57,8 +119,13 @@ pub struct ComputeMerchantReputation<'info> {
     #[account(mut)]
     pub reputation: Account<'info, MerchantReputation>,
     
-    // pub oracle_authority: Signer<'info>,
+    #[account(seeds = [b"oracle_authority"], bump = oracle_authority.bump)]
+    pub oracle_authority: Account<'info, OracleAuthority>,
+    
+    #[account(constraint = oracle_authority.is_signer(&crank.key()) @ ViralSyncError::UnauthorizedOracle)]
     pub crank: Signer<'info>,
+    
+    // remaining_accounts: co-signing oracle keys, counted towards `reputation_threshold`
 }
 
 pub fn compute_merchant_reputation(
@@ -70,6 +137,12 @@ pub fn compute_merchant_reputation(
     avg_poi_score_top_referrers: u32,
     suspicion_score: u32,
 ) -> Result<()> {
+    let co_signers = ctx.remaining_accounts.iter().filter(|info| info.is_signer).map(|info| info.key);
+    let approvals = ctx.accounts.oracle_authority.count_approvals(std::iter::once(ctx.accounts.crank.key).chain(co_signers));
+    require!(
+        approvals >= ctx.accounts.oracle_authority.reputation_threshold,
+        ViralSyncError::OracleThresholdNotMet
+    );
     
     let rep = &mut ctx.accounts.reputation;
     rep.pct_redeemers_aged_over_30_days = pct_redeemers_aged_over_30_days;
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::state::{
+    merchant_config::MerchantConfig,
     commission_ledger::CommissionLedger,
     referral_record::ReferralRecord,
     token_generation::TokenGeneration,
@@ -7,6 +8,7 @@ use crate::state::{
 use crate::errors::ViralSyncError;
 
 #[derive(Accounts)]
+#[instruction(slot_idx: u8)]
 pub struct ProcessRedemptionSlot<'info> {
     #[account(mut)]
     pub redeemer: Signer<'info>, // Often relay/crank executing it
@@ -14,11 +16,31 @@ pub struct ProcessRedemptionSlot<'info> {
     #[account(mut)]
     pub redeemer_generation: Account<'info, TokenGeneration>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = redeemer_generation.referrer_slots.get(slot_idx as usize)
+            .is_some_and(|slot| slot.referral_record == referral_record.key()) @ ViralSyncError::ReferralSlotMismatch
+    )]
     pub referral_record: Account<'info, ReferralRecord>,
     
-    #[account(mut)]
+    // Referrer's own generation on this mint, credited with the successful referral
+    #[account(
+        mut,
+        constraint = referrer_generation.owner == referral_record.referrer @ ViralSyncError::ReferrerGenerationMismatch,
+        constraint = referrer_generation.mint == referral_record.mint @ ViralSyncError::ReferrerGenerationMismatch
+    )]
+    pub referrer_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        mut,
+        constraint = commission_ledger.referrer == referral_record.referrer @ ViralSyncError::AccessDenied,
+        constraint = commission_ledger.merchant == referral_record.merchant @ ViralSyncError::AccessDenied,
+        constraint = commission_ledger.mint == referral_record.mint @ ViralSyncError::AccessDenied
+    )]
     pub commission_ledger: Account<'info, CommissionLedger>,
+    
+    #[account(constraint = merchant_config.mint == referral_record.mint @ ViralSyncError::MerchantMintMismatch)]
+    pub merchant_config: Account<'info, MerchantConfig>,
 }
 
 pub fn process_redemption_slot(ctx: Context<ProcessRedemptionSlot>, slot_idx: u8) -> Result<()> {
@@ -34,7 +56,20 @@ pub fn process_redemption_slot(ctx: Context<ProcessRedemptionSlot>, slot_idx: u8
     
     let gen2_consumed = gen.redemption_slot_consumed[slot_idx as usize];
     
+    let now = Clock::get()?.unix_timestamp;
+    let referrer_gen = &mut ctx.accounts.referrer_generation;
+    let min_poi = ctx.accounts.merchant_config.min_poi_score_for_commission;
+    let full_weight = ctx.accounts.merchant_config.max_tokens_per_referral;
+    
+    // Eligibility reflects the score earned before this redemption, not the one it is about to add
+    let poi_eligible = min_poi == 0 || referrer_gen.effective_poi_score(now) >= min_poi;
+    
     if gen2_consumed > 0 && referral.is_active {
+        // Redemption-driven referrals always build influence, even when commission is withheld
+        referrer_gen.accrue_poi(now, gen2_consumed, full_weight);
+    }
+    
+    if gen2_consumed > 0 && referral.is_active && poi_eligible {
         // High precision commission calculation utilizing u128 to prevent overflow
         let commission_exact_u128 = (gen2_consumed as u128)
             .checked_mul(referral.committed_commission_bps as u128).unwrap();
@@ -60,6 +95,11 @@ pub fn process_redemption_slot(ctx: Context<ProcessRedemptionSlot>, slot_idx: u8
             ledger.highest_single_commission = commission_whole;
         }
         
+        // First commission-paying redemption marks the referral as successful
+        if referral.commission_earned == 0 && commission_whole > 0 {
+            referrer_gen.successful_referrals = referrer_gen.successful_referrals.saturating_add(1);
+        }
+        
         referral.commission_earned = referral.commission_earned.checked_add(commission_whole).unwrap();
     }
     
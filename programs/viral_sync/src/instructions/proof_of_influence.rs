@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    merchant_bond::MerchantBond,
+    token_generation::{TokenGeneration, MAX_POI_GENERATIONS},
+};
+use crate::errors::ViralSyncError;
+
+#[event]
+pub struct PoiScoreUpdated {
+    pub owner: Pubkey,
+    pub generation: Pubkey,
+    pub poi_score: u32,
+    pub generations_counted: u8,
+}
+
+#[derive(Accounts)]
+pub struct ComputePoiScore<'info> {
+    #[account(mut, has_one = owner)]
+    pub token_generation: Account<'info, TokenGeneration>,
+    
+    // Only the owner refreshes their score; leaving generations out can only lower it
+    pub owner: Signer<'info>,
+    
+    // Bond on the target generation's mint; without one, momentum there does not count
+    #[account(constraint = merchant_bond.mint == token_generation.mint @ ViralSyncError::InvalidPoiGeneration)]
+    pub merchant_bond: Option<Account<'info, MerchantBond>>,
+    
+    // remaining_accounts: (TokenGeneration, MerchantBond) pairs for the owner's generations on
+    // other merchants' mints, each followed by that mint's bond
+}
+
+// Deterministic cross-merchant score: the sum of the decayed referral momentum on every mint
+// whose merchant is bonded, so self-made merchants cannot farm it
+pub fn compute_poi_score<'info>(ctx: Context<'_, '_, 'info, 'info, ComputePoiScore<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let owner = ctx.accounts.owner.key();
+    let target = &mut ctx.accounts.token_generation;
+    
+    require!(
+        ctx.remaining_accounts.len().is_multiple_of(2) && ctx.remaining_accounts.len() / 2 < MAX_POI_GENERATIONS,
+        ViralSyncError::InvalidPoiGeneration
+    );
+    
+    let mut seen_mints: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len() / 2 + 1);
+    seen_mints.push(target.mint);
+    
+    let mut total: u64 = 0;
+    let mut counted: u8 = 0;
+    if ctx.accounts.merchant_bond.as_ref().is_some_and(|bond| bond.backs_poi()) {
+        total = target.poi_momentum_at(now);
+        counted += 1;
+    }
+    
+    for pair in ctx.remaining_accounts.chunks(2) {
+        // Account::try_from enforces program ownership and each discriminator
+        let other: Account<TokenGeneration> = Account::try_from(&pair[0])?;
+        let bond: Account<MerchantBond> = Account::try_from(&pair[1])?;
+        require!(other.owner == owner, ViralSyncError::InvalidPoiGeneration);
+        require!(bond.mint == other.mint, ViralSyncError::InvalidPoiGeneration);
+        require!(!seen_mints.contains(&other.mint), ViralSyncError::InvalidPoiGeneration);
+        seen_mints.push(other.mint);
+        
+        if bond.backs_poi() {
+            total = total.saturating_add(other.poi_momentum_at(now));
+            counted += 1;
+        }
+    }
+    
+    target.poi_score = total.min(u32::MAX as u64) as u32;
+    target.poi_updated_at = now;
+    
+    emit!(PoiScoreUpdated {
+        owner,
+        generation: target.key(),
+        poi_score: target.poi_score,
+        generations_counted: counted,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,322 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked, Burn, burn};
+use crate::state::{
+    session_key::{
+        SessionKey, SESSION_PERMISSION_SHARE, SESSION_PERMISSION_REDEEM, SESSION_PERMISSION_BURN,
+        SESSION_PERMISSION_CLAIM_COMMISSION, SESSION_PERMISSION_FINALIZE,
+    },
+    token_generation::TokenGeneration,
+    merchant_config::VaultEntry,
+    escrow::{Escrow, EscrowTerms},
+};
+use crate::instructions::burn_tokens::debit_for_burn;
+use crate::instructions::claim_commission::*;
+use crate::instructions::finalize_inbound::finalize_pending;
+use crate::instructions::escrows::{open_escrow, emit_created, mark_intermediary, buckets, record_deposit};
+use crate::errors::ViralSyncError;
+
+// Delegate-signed variants of the user actions. The relayer signs with the session delegate and
+// the session PDA moves tokens as the SPL delegate, so the owner must first `approve` the session
+// PDA on their token account (typically in the same transaction as create_session_key).
+
+#[event]
+pub struct SessionKeySpent {
+    pub session_key: Pubkey,
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub tokens_spent: u64,
+}
+
+// ── SHARE VIA ESCROW ────────────────────────────────────────────────────────
+#[derive(Accounts)]
+#[instruction(nonce: u64, amount: u64, terms: EscrowTerms)]
+pub struct CreateEscrowShareWithSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", source_generation.key().as_ref(), delegate.key().as_ref(), &session_key.epoch.to_le_bytes()],
+        bump = session_key.bump,
+        has_one = delegate
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    
+    // Relayer; also pays rent for the escrow account
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+    
+    #[account(mut)]
+    pub source_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        init,
+        payer = delegate,
+        space = Escrow::space_for(terms.max_claimers),
+        seeds = [b"escrow", mint.key().as_ref(), source_generation.owner.as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    
+    #[account(
+        mut,
+        seeds = [b"gen_v4", mint.key().as_ref(), escrow.key().as_ref()],
+        bump = escrow_generation.bump
+    )]
+    pub escrow_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        mut,
+        constraint = source_ata.owner == source_generation.owner @ ViralSyncError::AccessDenied,
+        constraint = source_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub source_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = escrow_ata.owner == escrow.key() @ ViralSyncError::AccessDenied,
+        constraint = escrow_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub escrow_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_escrow_share_with_session(
+    ctx: Context<CreateEscrowShareWithSession>,
+    nonce: u64,
+    amount: u64,
+    terms: EscrowTerms,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let source_generation_key = ctx.accounts.source_generation.key();
+    require!(ctx.accounts.session_key.allows_escrow_recipient(terms.recipient), ViralSyncError::SessionDestinationNotAllowed);
+    ctx.accounts.session_key.authorize_spend(source_generation_key, ctx.accounts.source_generation.session_epoch, SESSION_PERMISSION_SHARE, amount, None, now)?;
+    
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.bump = ctx.bumps.escrow;
+    escrow.mint = ctx.accounts.mint.key();
+    escrow.creator = ctx.accounts.source_generation.owner;
+    escrow.nonce = nonce;
+    open_escrow(escrow, &terms, now)?;
+    
+    // Mirrors create_escrow_share: the escrow remembers who shared
+    let escrow_gen = &mut ctx.accounts.escrow_generation;
+    mark_intermediary(escrow_gen, &ctx.accounts.escrow);
+    // Flush now so the hook, which reads account data during the CPI, sees the flag
+    escrow_gen.exit(&crate::ID)?;
+    
+    let balance_before = ctx.accounts.escrow_ata.amount;
+    let buckets_before = buckets(&ctx.accounts.source_generation);
+    
+    let session = &ctx.accounts.session_key;
+    let epoch = session.epoch.to_le_bytes();
+    let bump = [session.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"session", source_generation_key.as_ref(), session.delegate.as_ref(), &epoch, &bump]];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.source_ata.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.escrow_ata.to_account_info(),
+        authority: ctx.accounts.session_key.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    
+    // The hook rewrote both generations during the CPI; don't clobber them on exit
+    ctx.accounts.source_generation.reload()?;
+    ctx.accounts.escrow_generation.reload()?;
+    
+    ctx.accounts.escrow_ata.reload()?;
+    ctx.accounts.escrow.amount = ctx.accounts.escrow_ata.amount.checked_sub(balance_before).unwrap();
+    record_deposit(&mut ctx.accounts.escrow, buckets_before, &ctx.accounts.source_generation);
+    
+    emit_created(&ctx.accounts.escrow);
+    emit_spent(&ctx.accounts.session_key, amount);
+    Ok(())
+}
+
+// ── REDEEM AT A REGISTERED VAULT ────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct RedeemWithSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", token_generation.key().as_ref(), delegate.key().as_ref(), &session_key.epoch.to_le_bytes()],
+        bump = session_key.bump,
+        has_one = delegate
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    
+    pub delegate: Signer<'info>,
+    
+    #[account(mut)]
+    pub token_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        mut,
+        constraint = owner_ata.owner == token_generation.owner @ ViralSyncError::AccessDenied,
+        constraint = owner_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub owner_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"vault_entry", mint.key().as_ref(), vault_entry.vault.as_ref()],
+        bump = vault_entry.bump,
+        constraint = vault_entry.is_active @ ViralSyncError::AccessDenied
+    )]
+    pub vault_entry: Account<'info, VaultEntry>,
+    
+    #[account(
+        mut,
+        constraint = vault_ata.owner == vault_entry.vault @ ViralSyncError::AccessDenied,
+        constraint = vault_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn redeem_with_session(ctx: Context<RedeemWithSession>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let generation_key = ctx.accounts.token_generation.key();
+    let destination = ctx.accounts.vault_ata.owner;
+    ctx.accounts.session_key.authorize_spend(generation_key, ctx.accounts.token_generation.session_epoch, SESSION_PERMISSION_REDEEM, amount, Some(destination), now)?;
+    
+    let session = &ctx.accounts.session_key;
+    let epoch = session.epoch.to_le_bytes();
+    let bump = [session.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"session", generation_key.as_ref(), session.delegate.as_ref(), &epoch, &bump]];
+    
+    // The hook sees a transfer into a registered vault and runs the normal redemption path
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.owner_ata.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault_ata.to_account_info(),
+        authority: ctx.accounts.session_key.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+    
+    ctx.accounts.token_generation.reload()?;
+    
+    emit_spent(&ctx.accounts.session_key, amount);
+    Ok(())
+}
+
+// ── BURN ────────────────────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct BurnTokensWithSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", token_generation.key().as_ref(), delegate.key().as_ref(), &session_key.epoch.to_le_bytes()],
+        bump = session_key.bump,
+        has_one = delegate
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    
+    pub delegate: Signer<'info>,
+    
+    #[account(mut)]
+    pub token_generation: Account<'info, TokenGeneration>,
+    
+    #[account(
+        mut,
+        constraint = owner_ata.owner == token_generation.owner @ ViralSyncError::AccessDenied,
+        constraint = owner_ata.mint == mint.key() @ ViralSyncError::InvalidTokenAccount
+    )]
+    pub owner_ata: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn burn_tokens_with_session(ctx: Context<BurnTokensWithSession>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let generation_key = ctx.accounts.token_generation.key();
+    ctx.accounts.session_key.authorize_spend(generation_key, ctx.accounts.token_generation.session_epoch, SESSION_PERMISSION_BURN, amount, None, now)?;
+    
+    // Burns bypass the transfer hook, so the ledger is debited here exactly as burn_tokens does
+    debit_for_burn(&mut ctx.accounts.token_generation, amount)?;
+    
+    let session = &ctx.accounts.session_key;
+    let epoch = session.epoch.to_le_bytes();
+    let bump = [session.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"session", generation_key.as_ref(), session.delegate.as_ref(), &epoch, &bump]];
+    
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.owner_ata.to_account_info(),
+        authority: ctx.accounts.session_key.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+    burn(cpi_ctx, amount)?;
+    
+    emit_spent(&ctx.accounts.session_key, amount);
+    Ok(())
+}
+
+// ── CLAIM COMMISSION ────────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct ClaimCommissionWithSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", payout.referrer_generation.key().as_ref(), delegate.key().as_ref(), &session_key.epoch.to_le_bytes()],
+        bump = session_key.bump,
+        has_one = delegate
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    
+    pub delegate: Signer<'info>,
+    
+    pub payout: CommissionPayout<'info>,
+}
+
+pub fn claim_commission_with_session(ctx: Context<ClaimCommissionWithSession>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    // Claims move nothing out of the owner's wallet, so they draw nothing from the spend caps
+    let payout = &ctx.accounts.payout;
+    let generation_key = payout.referrer_generation.key();
+    let epoch = payout.referrer_generation.session_epoch;
+    ctx.accounts.session_key.authorize_spend(generation_key, epoch, SESSION_PERMISSION_CLAIM_COMMISSION, 0, None, now)?;
+    
+    ctx.accounts.payout.claim(ctx.bumps.payout.treasury_signer)
+}
+
+// ── FINALIZE INBOUND ────────────────────────────────────────────────────────
+#[derive(Accounts)]
+pub struct FinalizeInboundWithSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", dest_generation.key().as_ref(), delegate.key().as_ref(), &session_key.epoch.to_le_bytes()],
+        bump = session_key.bump,
+        has_one = delegate
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    
+    pub delegate: Signer<'info>,
+    
+    #[account(mut)]
+    pub dest_generation: Account<'info, TokenGeneration>,
+}
+
+pub fn finalize_inbound_with_session(ctx: Context<FinalizeInboundWithSession>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let generation_key = ctx.accounts.dest_generation.key();
+    ctx.accounts.session_key.authorize_spend(generation_key, ctx.accounts.dest_generation.session_epoch, SESSION_PERMISSION_FINALIZE, 0, None, now)?;
+    
+    finalize_pending(&mut ctx.accounts.dest_generation)
+}
+
+fn emit_spent(session: &Account<SessionKey>, amount: u64) {
+    emit!(SessionKeySpent {
+        session_key: session.key(),
+        delegate: session.delegate,
+        amount,
+        tokens_spent: session.tokens_spent,
+    });
+}
@@ -1,14 +1,36 @@
 use anchor_lang::prelude::*;
-use crate::state::{session_key::SessionKey, token_generation::TokenGeneration};
+use crate::state::{
+    session_key::{SessionKey, MAX_SESSION_DESTINATIONS, SESSION_PERMISSION_ALL},
+    token_generation::TokenGeneration,
+};
 use crate::errors::ViralSyncError;
 
+#[event]
+pub struct SessionKeyExtended {
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AllSessionsRevoked {
+    pub token_generation: Pubkey,
+    pub new_epoch: u32,
+}
+
+#[event]
+pub struct StaleSessionClosed {
+    pub session_key: Pubkey,
+    pub authority: Pubkey,
+    pub rent_recovered: u64,
+}
+
 #[derive(Accounts)]
 pub struct CreateSessionKey<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 1,
-        seeds = [b"session", token_generation.key().as_ref(), delegate.key().as_ref()],
+        space = SessionKey::LEN,
+        seeds = [b"session", token_generation.key().as_ref(), delegate.key().as_ref(), &token_generation.session_epoch.to_le_bytes()],
         bump
     )]
     pub session_key: Account<'info, SessionKey>,
@@ -27,16 +49,20 @@ pub struct CreateSessionKey<'info> {
 pub fn create_session_key(
     ctx: Context<CreateSessionKey>, 
     expires_at: i64, 
-    max_tokens_per_session: u64
+    max_tokens_per_session: u64,
+    permissions: u8,
+    daily_spend_limit: u64,
+    allowed_destinations: Vec<Pubkey>,
 ) -> Result<()> {
     let session = &mut ctx.accounts.session_key;
     let gen = &ctx.accounts.token_generation;
     
     // Ensure only the true owner can delegate power over their PDA
-    require!(gen.owner == ctx.accounts.authority.key(), ViralSyncError::AccessDenied); // Map generic AccessDenied
+    require!(gen.owner == ctx.accounts.authority.key(), ViralSyncError::AccessDenied);
     
     // Bound the session tightly to prevent infinite authority
-    require!(expires_at > Clock::get()?.unix_timestamp, ViralSyncError::TokensExpired); 
+    require!(expires_at > Clock::get()?.unix_timestamp, ViralSyncError::InvalidSessionExpiry);
+    require!(allowed_destinations.len() <= MAX_SESSION_DESTINATIONS, ViralSyncError::TooManySessionDestinations);
     
     session.bump = ctx.bumps.session_key;
     session.authority = ctx.accounts.authority.key();
@@ -47,6 +73,102 @@ pub fn create_session_key(
     session.tokens_spent = 0;
     session.is_active = true;
     
+    session.permissions = permissions & SESSION_PERMISSION_ALL;
+    session.daily_spend_limit = daily_spend_limit;
+    session.spend_day = 0;
+    session.spent_today = 0;
+    session.allowed_destination_count = allowed_destinations.len() as u8;
+    session.allowed_destinations = [Pubkey::default(); MAX_SESSION_DESTINATIONS];
+    session.allowed_destinations[..allowed_destinations.len()].copy_from_slice(&allowed_destinations);
+    
+    session.epoch = gen.session_epoch;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExtendSessionKey<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = session_key.target_generation == token_generation.key() @ ViralSyncError::SessionGenerationMismatch
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    
+    pub token_generation: Account<'info, TokenGeneration>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn extend_session_key(ctx: Context<ExtendSessionKey>, expires_at: i64) -> Result<()> {
+    let session = &mut ctx.accounts.session_key;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Revoked or epoch-invalidated keys must be replaced, not resurrected
+    require!(
+        session.is_valid(now, ctx.accounts.token_generation.session_epoch),
+        ViralSyncError::SessionKeyInvalid
+    );
+    require!(expires_at > now, ViralSyncError::InvalidSessionExpiry);
+    
+    session.expires_at = expires_at;
+    
+    emit!(SessionKeyExtended { session_key: session.key(), expires_at });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeAllSessions<'info> {
+    #[account(mut, has_one = owner)]
+    pub token_generation: Account<'info, TokenGeneration>,
+    
+    pub owner: Signer<'info>,
+}
+
+// Lost-device escape hatch: one write invalidates every outstanding delegate at once.
+// New keys derive from the new epoch, so the same delegate can be re-issued immediately.
+pub fn revoke_all_sessions(ctx: Context<RevokeAllSessions>) -> Result<()> {
+    let gen = &mut ctx.accounts.token_generation;
+    gen.session_epoch = gen.session_epoch.checked_add(1).ok_or(ViralSyncError::MathOverflow)?;
+    
+    emit!(AllSessionsRevoked { token_generation: gen.key(), new_epoch: gen.session_epoch });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseStaleSessionKey<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority,
+        constraint = session_key.target_generation == token_generation.key() @ ViralSyncError::SessionGenerationMismatch
+    )]
+    pub session_key: Account<'info, SessionKey>,
+    
+    pub token_generation: Account<'info, TokenGeneration>,
+    
+    /// CHECK: Rent goes back to the wallet that funded the key (bound via has_one)
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+    
+    pub caller: Signer<'info>, // Permissionless! Anyone can crank this
+}
+
+pub fn close_stale_session_key(ctx: Context<CloseStaleSessionKey>) -> Result<()> {
+    let session = &ctx.accounts.session_key;
+    let now = Clock::get()?.unix_timestamp;
+    
+    require!(
+        !session.is_valid(now, ctx.accounts.token_generation.session_epoch),
+        ViralSyncError::SessionKeyStillActive
+    );
+    
+    emit!(StaleSessionClosed {
+        session_key: session.key(),
+        authority: session.authority,
+        rent_recovered: session.to_account_info().lamports(),
+    });
+    
     Ok(())
 }
 
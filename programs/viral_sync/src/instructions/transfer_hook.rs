@@ -106,12 +106,24 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
     
     let is_from_merchant = src_owner == config.merchant;
     let is_from_treasury = src_gen.is_treasury;
+    // A forged intermediary flag would skip the referral limits and re-attribute shares
+    require!(!src_gen.is_intermediary || src_gen.is_valid_intermediary(), ViralSyncError::InvalidIntermediary);
+    require!(!dst_gen.is_intermediary || dst_gen.is_valid_intermediary(), ViralSyncError::InvalidIntermediary);
     let is_src_intermediary = src_gen.is_intermediary;
     let is_dst_intermediary = dst_gen.is_intermediary;
+    
+    // ── SUNSET ──
+    // Holders exit through redeem_bond_share or burns; only unclaimed escrows may still unwind to their creators
+    if !config.is_active {
+        let is_escrow_return = is_src_intermediary
+            && src_gen.original_sender != Pubkey::default()
+            && dst_owner == src_gen.original_sender;
+        require!(is_escrow_return, ViralSyncError::MerchantSunset);
+    }
     let is_to_vault = is_registered_vault(&ctx.accounts.vault_entry);
     let is_dex_involved = src_gen.is_dex_pool || dst_gen.is_dex_pool;
     
-    // ── TREASURY TRANSFER (Commission payout) ──
+    // ── TREASURY TRANSFER (Commission payouts and watchdog rewards) ──
     if is_from_treasury {
         let entry = InboundEntry {
             referrer: Pubkey::default(),
@@ -128,6 +140,7 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
         } else {
             dst_gen.gen1_balance = dst_gen.gen1_balance.checked_add(amount).unwrap();
         }
+        dst_gen.record_received(amount);
         return Ok(());
     }
     
@@ -138,6 +151,7 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
         }
         if !dst_gen.is_dex_pool && !is_dst_intermediary {
             dst_gen.dead_balance = dst_gen.dead_balance.checked_add(amount).unwrap();
+            dst_gen.record_received(amount);
             emit!(DexTransferDetected { from: src_owner, to: dst_owner, amount });
         }
         return Ok(());
@@ -160,6 +174,7 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
         src_gen.redemption_slot = Clock::get()?.slot;
         src_gen.redemption_gen2_consumed = gen2_consumed;
         src_gen.redemption_slots_settled = 0;
+        src_gen.record_redemption(amount);
         
         let total_gen2_before = src_gen.gen2_balance.checked_add(gen2_consumed).unwrap();
         for i in 0..src_gen.active_referrer_slots as usize {
@@ -189,6 +204,7 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
         } else {
             dst_gen.dead_balance = dst_gen.dead_balance.checked_add(amount).unwrap();
         }
+        dst_gen.record_received(amount);
         if dst_gen.first_received_at == 0 {
             dst_gen.first_received_at = Clock::get()?.unix_timestamp;
         }
@@ -198,8 +214,18 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
     
     // ── INTERMEDIARY ESCROW RELEASE ──
     if is_src_intermediary {
-        let effective_referrer = src_gen.original_sender;
+        // Unclaimed escrow returning to its creator: harvest_expired_escrows restores the buckets
+        // the deposit was drawn from, so nothing is credited here
+        if dst_owner == src_gen.original_sender && src_gen.original_sender != Pubkey::default() {
+            return Ok(());
+        }
+        
+        let mut effective_referrer = src_gen.original_sender;
         if !is_dst_intermediary {
+            // Referrer identity was checked when the escrow was funded; only the claimant is left
+            if !config.referred_identity_ok(dst_gen) {
+                effective_referrer = Pubkey::default();
+            }
             let entry_type = if effective_referrer != Pubkey::default() { GenSource::ViralShare } else { GenSource::DeadPass };
             let entry = InboundEntry {
                 referrer: effective_referrer,
@@ -217,6 +243,7 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
             } else {
                 dst_gen.dead_balance = dst_gen.dead_balance.checked_add(amount).unwrap();
             }
+            dst_gen.record_received(amount);
             if dst_gen.first_received_at == 0 {
                 dst_gen.first_received_at = Clock::get()?.unix_timestamp;
             }
@@ -227,16 +254,9 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
     // ── PEER TRANSFER ──
     let held_secs = Clock::get()?.unix_timestamp.saturating_sub(src_gen.first_received_at);
     require!(held_secs >= config.min_hold_before_share_secs, ViralSyncError::HoldPeriodNotMet);
-    require!(amount >= config.min_tokens_per_referral, ViralSyncError::BelowMinimum);
-    require!(amount <= config.max_tokens_per_referral, ViralSyncError::ExceedsMaximum);
-    
-    let today_index = Clock::get()?.slot / config.slots_per_day.max(1);
-    if src_gen.share_limit_day == today_index {
-        require!(src_gen.shares_today < config.max_referrals_per_wallet_per_day, ViralSyncError::DailyShareLimitExceeded);
-        src_gen.shares_today += 1;
-    } else {
-        src_gen.share_limit_day = today_index;
-        src_gen.shares_today = 1;
+    // Escrow deposits are metered per claim in claim_escrow instead
+    if !is_dst_intermediary {
+        config.enforce_referral_limits(src_gen, amount, Clock::get()?.slot)?;
     }
     
     if src_gen.gen1_balance == 0 && !config.allow_second_gen_transfer {
@@ -249,8 +269,9 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
     src_gen.gen1_balance -= from_gen1;
     src_gen.gen2_balance -= from_gen2;
     src_gen.dead_balance -= from_dead;
+    src_gen.record_share(amount);
     
-    let (entry_type, effective_referrer) = if from_gen1 > 0 {
+    let (mut entry_type, mut effective_referrer) = if from_gen1 > 0 {
         (GenSource::ViralShare, src_owner)
     } else if from_gen2 > 0 {
         if config.allow_second_gen_transfer {
@@ -262,6 +283,19 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
         (GenSource::DeadPass, Pubkey::default())
     };
     
+    // Identity-gated merchants: the transfer still succeeds, but earns no referral attribution
+    if entry_type == GenSource::ViralShare {
+        let referred_ok = is_dst_intermediary || config.referred_identity_ok(dst_gen);
+        if !config.referrer_identity_ok(src_gen) || !referred_ok {
+            entry_type = GenSource::DeadPass;
+            effective_referrer = Pubkey::default();
+            if is_dst_intermediary {
+                // Escrow releases would otherwise re-attribute to the unverified sender
+                dst_gen.original_sender = Pubkey::default();
+            }
+        }
+    }
+    
     if !is_dst_intermediary {
         let entry = InboundEntry {
             referrer: effective_referrer,
@@ -280,6 +314,7 @@ pub fn execute_transfer_hook(ctx: Context<ExecuteHook>, amount: u64) -> Result<(
         } else {
             dst_gen.dead_balance = dst_gen.dead_balance.checked_add(amount).unwrap();
         }
+        dst_gen.record_received(amount);
         if dst_gen.first_received_at == 0 {
             dst_gen.first_received_at = Clock::get()?.unix_timestamp;
         }
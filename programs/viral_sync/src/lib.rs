@@ -6,6 +6,7 @@ pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::escrow::EscrowTerms;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -22,6 +23,10 @@ pub mod viral_sync {
         instructions::init_treasury_token_generation::handler(ctx)
     }
 
+    pub fn migrate_token_generation(ctx: Context<MigrateTokenGeneration>) -> Result<()> {
+        instructions::migrate_token_generation::migrate_token_generation(ctx)
+    }
+
     pub fn create_mint_and_config(
         ctx: Context<CreateMintAndConfig>,
         commission_rate_bps: u16,
@@ -35,6 +40,21 @@ pub mod viral_sync {
         instructions::merchant_init::issue_first_tokens_and_lock(ctx, amount)
     }
 
+    pub fn set_min_poi_score(ctx: Context<UpdateMerchantConfig>, min_poi_score_for_commission: u32) -> Result<()> {
+        instructions::merchant_init::set_min_poi_score(ctx, min_poi_score_for_commission)
+    }
+
+    pub fn set_identity_requirements(
+        ctx: Context<UpdateMerchantConfig>,
+        require_identity_for_referrer: bool,
+        require_identity_for_referred: bool,
+        allowed_identity_providers: u16,
+    ) -> Result<()> {
+        instructions::merchant_init::set_identity_requirements(
+            ctx, require_identity_for_referrer, require_identity_for_referred, allowed_identity_providers
+        )
+    }
+
     // Phase 2
     pub fn initialize_extra_account_meta_list(ctx: Context<InitExtraAccountMetaList>) -> Result<()> {
         instructions::transfer_hook::initialize_extra_account_meta_list(ctx)
@@ -66,12 +86,17 @@ pub mod viral_sync {
     }
 
     // Phase 4: Escrows & Link Generation
-    pub fn create_escrow_share(ctx: Context<CreateEscrowShare>, amount: u64) -> Result<()> {
-        instructions::escrows::create_escrow_share(ctx, amount)
+    pub fn create_escrow_share(
+        ctx: Context<CreateEscrowShare>,
+        nonce: u64,
+        amount: u64,
+        terms: EscrowTerms,
+    ) -> Result<()> {
+        instructions::escrows::create_escrow_share(ctx, nonce, amount, terms)
     }
 
-    pub fn claim_escrow(ctx: Context<ClaimEscrow>, amount: u64) -> Result<()> {
-        instructions::escrows::claim_escrow(ctx, amount)
+    pub fn claim_escrow(ctx: Context<ClaimEscrow>, amount: u64, claim_code: Option<Vec<u8>>) -> Result<()> {
+        instructions::escrows::claim_escrow(ctx, amount, claim_code)
     }
 
     pub fn harvest_expired_escrows(ctx: Context<HarvestExpiredEscrows>) -> Result<()> {
@@ -124,10 +149,59 @@ pub mod viral_sync {
         )
     }
 
+    pub fn init_oracle_authority(ctx: Context<InitOracleAuthority>) -> Result<()> {
+        instructions::oracles::init_oracle_authority(ctx)
+    }
+
+    pub fn add_oracle_signer(ctx: Context<ManageOracleAuthority>, signer: Pubkey) -> Result<()> {
+        instructions::oracles::add_oracle_signer(ctx, signer)
+    }
+
+    pub fn remove_oracle_signer(ctx: Context<ManageOracleAuthority>, signer: Pubkey) -> Result<()> {
+        instructions::oracles::remove_oracle_signer(ctx, signer)
+    }
+
+    pub fn set_reputation_threshold(ctx: Context<ManageOracleAuthority>, threshold: u8) -> Result<()> {
+        instructions::oracles::set_reputation_threshold(ctx, threshold)
+    }
+
+    pub fn compute_poi_score<'info>(ctx: Context<'_, '_, 'info, 'info, ComputePoiScore<'info>>) -> Result<()> {
+        instructions::proof_of_influence::compute_poi_score(ctx)
+    }
+
+    // Identity commitments (sybil resistance)
+    pub fn init_identity_registry(ctx: Context<InitIdentityRegistry>) -> Result<()> {
+        instructions::identity::init_identity_registry(ctx)
+    }
+
+    pub fn set_identity_provider(ctx: Context<SetIdentityProvider>, provider_id: u16, signer: Pubkey) -> Result<()> {
+        instructions::identity::set_identity_provider(ctx, provider_id, signer)
+    }
+
+    pub fn bind_identity(ctx: Context<BindIdentity>, provider_id: u16, commitment: [u8; 32]) -> Result<()> {
+        instructions::identity::bind_identity(ctx, provider_id, commitment)
+    }
+
+    pub fn unbind_identity(ctx: Context<UnbindIdentity>) -> Result<()> {
+        instructions::identity::unbind_identity(ctx)
+    }
+
     pub fn redeem_with_geo(ctx: Context<RedeemWithGeo>, lat_micro: i32, lng_micro: i32, signature: Vec<u8>) -> Result<()> {
         instructions::geo_fencing::redeem_with_geo(ctx, lat_micro, lng_micro, signature)
     }
 
+    pub fn init_merchant_bond(ctx: Context<InitMerchantBond>) -> Result<()> {
+        instructions::bond_management::init_merchant_bond(ctx)
+    }
+
+    pub fn deposit_bond(ctx: Context<DepositBond>, amount: u64) -> Result<()> {
+        instructions::bond_management::deposit_bond(ctx, amount)
+    }
+
+    pub fn request_bond_unlock(ctx: Context<RequestBondUnlock>) -> Result<()> {
+        instructions::bond_management::request_bond_unlock(ctx)
+    }
+
     pub fn withdraw_bond(ctx: Context<WithdrawBond>, amount: u64) -> Result<()> {
         instructions::bond_management::withdraw_bond(ctx, amount)
     }
@@ -137,7 +211,11 @@ pub mod viral_sync {
         instructions::bond_management::initiate_close_merchant(ctx)
     }
 
-    pub fn finalize_close_merchant(ctx: Context<FinalizeCloseMerchant>) -> Result<()> {
+    pub fn cancel_close_merchant(ctx: Context<CancelCloseMerchant>) -> Result<()> {
+        instructions::bond_management::cancel_close_merchant(ctx)
+    }
+
+    pub fn finalize_close_merchant<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeCloseMerchant<'info>>) -> Result<()> {
         instructions::bond_management::finalize_close_merchant(ctx)
     }
 
@@ -153,12 +231,106 @@ pub mod viral_sync {
         instructions::disputes::resolve_expired_dispute(ctx)
     }
 
+    pub fn init_arbitration(ctx: Context<InitArbitration>, arbiter: Pubkey) -> Result<()> {
+        instructions::disputes::init_arbitration(ctx, arbiter)
+    }
+
+    pub fn set_arbiter(ctx: Context<SetArbiter>, arbiter: Pubkey) -> Result<()> {
+        instructions::disputes::set_arbiter(ctx, arbiter)
+    }
+
+    pub fn respond_to_dispute(ctx: Context<RespondToDispute>, evidence_hash: [u8; 32]) -> Result<()> {
+        instructions::disputes::respond_to_dispute(ctx, evidence_hash)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, uphold: bool) -> Result<()> {
+        instructions::disputes::resolve_dispute(ctx, uphold)
+    }
+
+    pub fn join_juror_pool(ctx: Context<JoinJurorPool>, stake_lamports: u64) -> Result<()> {
+        instructions::jury::join_juror_pool(ctx, stake_lamports)
+    }
+
+    pub fn request_juror_exit(ctx: Context<RequestJurorExit>) -> Result<()> {
+        instructions::jury::request_juror_exit(ctx)
+    }
+
+    pub fn withdraw_juror_stake(ctx: Context<WithdrawJurorStake>) -> Result<()> {
+        instructions::jury::withdraw_juror_stake(ctx)
+    }
+
+    pub fn summon_jurors(ctx: Context<SummonJurors>) -> Result<()> {
+        instructions::jury::summon_jurors(ctx)
+    }
+
+    pub fn draw_jurors(ctx: Context<DrawJurors>) -> Result<()> {
+        instructions::jury::draw_jurors(ctx)
+    }
+
+    pub fn commit_juror_vote(ctx: Context<CastJurorVote>, commitment: [u8; 32]) -> Result<()> {
+        instructions::jury::commit_juror_vote(ctx, commitment)
+    }
+
+    pub fn reveal_juror_vote(ctx: Context<CastJurorVote>, uphold: bool, salt: [u8; 32]) -> Result<()> {
+        instructions::jury::reveal_juror_vote(ctx, uphold, salt)
+    }
+
+    pub fn tally_dispute_votes<'info>(ctx: Context<'_, '_, 'info, 'info, TallyDisputeVotes<'info>>) -> Result<()> {
+        instructions::jury::tally_dispute_votes(ctx)
+    }
+
     // Phase 9: Seamless Client Architecture & Relayer (On-Chain)
-    pub fn create_session_key(ctx: Context<CreateSessionKey>, expires_at: i64, max_tokens_per_session: u64) -> Result<()> {
-        instructions::session_management::create_session_key(ctx, expires_at, max_tokens_per_session)
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        expires_at: i64,
+        max_tokens_per_session: u64,
+        permissions: u8,
+        daily_spend_limit: u64,
+        allowed_destinations: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::session_management::create_session_key(
+            ctx, expires_at, max_tokens_per_session, permissions, daily_spend_limit, allowed_destinations
+        )
     }
 
     pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
         instructions::session_management::revoke_session_key(ctx)
     }
+
+    pub fn extend_session_key(ctx: Context<ExtendSessionKey>, expires_at: i64) -> Result<()> {
+        instructions::session_management::extend_session_key(ctx, expires_at)
+    }
+
+    pub fn revoke_all_sessions(ctx: Context<RevokeAllSessions>) -> Result<()> {
+        instructions::session_management::revoke_all_sessions(ctx)
+    }
+
+    pub fn close_stale_session_key(ctx: Context<CloseStaleSessionKey>) -> Result<()> {
+        instructions::session_management::close_stale_session_key(ctx)
+    }
+
+    pub fn create_escrow_share_with_session(
+        ctx: Context<CreateEscrowShareWithSession>,
+        nonce: u64,
+        amount: u64,
+        terms: EscrowTerms,
+    ) -> Result<()> {
+        instructions::session_actions::create_escrow_share_with_session(ctx, nonce, amount, terms)
+    }
+
+    pub fn redeem_with_session(ctx: Context<RedeemWithSession>, amount: u64) -> Result<()> {
+        instructions::session_actions::redeem_with_session(ctx, amount)
+    }
+
+    pub fn burn_tokens_with_session(ctx: Context<BurnTokensWithSession>, amount: u64) -> Result<()> {
+        instructions::session_actions::burn_tokens_with_session(ctx, amount)
+    }
+
+    pub fn claim_commission_with_session(ctx: Context<ClaimCommissionWithSession>) -> Result<()> {
+        instructions::session_actions::claim_commission_with_session(ctx)
+    }
+
+    pub fn finalize_inbound_with_session(ctx: Context<FinalizeInboundWithSession>) -> Result<()> {
+        instructions::session_actions::finalize_inbound_with_session(ctx)
+    }
 }
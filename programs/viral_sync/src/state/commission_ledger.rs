@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::errors::ViralSyncError;
 
 #[account]
+#[derive(InitSpace)]
 pub struct CommissionLedger {
     pub bump: u8,
     pub referrer: Pubkey,
@@ -11,10 +13,41 @@ pub struct CommissionLedger {
     pub dust_tenths_accumulated: u32, // Accumulate fractional dust (units of 0.0001 tokens)
     
     pub frozen: bool,
-    pub frozen_amount: u64, // Disputed amounts frozen
+    pub frozen_amount: u64, // Sum of amounts frozen by open disputes
     
     pub total_earned: u64,
     pub total_claimed: u64,
     pub total_redemptions_driven: u64,
     pub highest_single_commission: u64,
 }
+
+impl CommissionLedger {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    // Concurrent disputes stack, but together they can never freeze more than is claimable
+    pub fn freeze(&mut self, amount: u64) -> Result<()> {
+        let frozen_amount = self.frozen_amount.checked_add(amount).ok_or(ViralSyncError::MathOverflow)?;
+        require!(amount > 0 && frozen_amount <= self.claimable, ViralSyncError::InvalidDisputeAmount);
+        self.frozen_amount = frozen_amount;
+        self.frozen = true;
+        Ok(())
+    }
+    
+    // Upheld dispute: its share of the frozen commission is forfeited. Returns the forfeited amount.
+    pub fn slash_frozen(&mut self, amount: u64) -> u64 {
+        let amount = amount.min(self.frozen_amount);
+        self.claimable = self.claimable.saturating_sub(amount);
+        self.unfreeze(amount);
+        amount
+    }
+    
+    // Dismissed dispute: the referrer keeps its share
+    pub fn release_frozen(&mut self, amount: u64) {
+        self.unfreeze(amount.min(self.frozen_amount));
+    }
+    
+    fn unfreeze(&mut self, amount: u64) {
+        self.frozen_amount -= amount;
+        self.frozen = self.frozen_amount > 0;
+    }
+}
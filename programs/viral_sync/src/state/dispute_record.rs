@@ -1,14 +1,21 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::state::jury::{JurorStake, MAX_JUROR_POOL, JURORS_PER_DISPUTE};
+use crate::errors::ViralSyncError;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum DisputeStatus {
     Pending,
     Dismissed,
     UpheldByTimeout,
     UpheldByVote,
+    InArbitration, // Merchant responded in time; awaiting a ruling
+    InVote,        // Jurors drawn; commit-reveal in progress
+    UpheldByArbiter,
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct DisputeRecord {
     pub bump: u8,
     pub merchant: Pubkey,
@@ -18,4 +25,96 @@ pub struct DisputeRecord {
     pub stake_lamports: u64,
     pub raised_at: i64,
     pub resolved_at: Option<i64>,
+
+    // Merchant response: 32-byte digest of the evidence CID (IPFS sha2-256 multihash or Arweave tx id)
+    pub evidence_hash: Option<[u8; 32]>,
+    pub responded_at: Option<i64>,
+    
+    pub disputed_amount: u64, // This dispute's share of the ledger's frozen_amount
+    pub escrow_bump: u8,
+}
+
+impl DisputeRecord {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
+
+// Program-owned lamport escrow ([dispute_escrow, dispute]). Everything above its rent-exempt floor is stake.
+#[account]
+#[derive(InitSpace)]
+pub struct DisputeEscrow {
+    pub bump: u8,
+    pub dispute: Pubkey,
+}
+
+impl DisputeEscrow {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
+
+// Protocol-wide arbitration settings. The arbiter rules on disputes the merchant has answered
+// when no juror vote reaches quorum.
+#[account]
+#[derive(InitSpace)]
+pub struct Arbitration {
+    pub bump: u8,
+    pub admin: Pubkey,
+    pub arbiter: Pubkey,
+    
+    // Staked juror pool; the first `juror_count` entries are live
+    pub juror_count: u8,
+    pub jurors: [Pubkey; MAX_JUROR_POOL],
+}
+
+impl Arbitration {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn pool(&self) -> &[Pubkey] {
+        &self.jurors[..self.juror_count as usize]
+    }
+    
+    pub fn add_juror(&mut self, juror: Pubkey) -> Result<()> {
+        require!(!self.pool().contains(&juror), ViralSyncError::JurorAlreadyRegistered);
+        require!((self.juror_count as usize) < MAX_JUROR_POOL, ViralSyncError::JurorPoolFull);
+        self.jurors[self.juror_count as usize] = juror;
+        self.juror_count += 1;
+        Ok(())
+    }
+    
+    pub fn is_full(&self) -> bool {
+        self.juror_count as usize >= MAX_JUROR_POOL
+    }
+    
+    // Swap-remove; pool order carries no meaning. Returns whether `juror` was in the pool.
+    pub fn remove_juror(&mut self, juror: &Pubkey) -> bool {
+        let Some(index) = self.pool().iter().position(|key| key == juror) else {
+            return false;
+        };
+        let last = self.juror_count as usize - 1;
+        self.jurors[index] = self.jurors[last];
+        self.jurors[last] = Pubkey::default();
+        self.juror_count -= 1;
+        true
+    }
+    
+    // A full pool admits a newcomer only by evicting a member who staked less. The evicted juror
+    // starts the exit cooldown, so the stake stays slashable for any vote already committed.
+    pub fn replace_juror(&mut self, evicted: &mut JurorStake, juror: Pubkey, stake_lamports: u64, now: i64) -> Result<()> {
+        require!(stake_lamports > evicted.stake_lamports, ViralSyncError::JurorStakeTooLow);
+        require!(self.remove_juror(&evicted.juror), ViralSyncError::JurorNotInPool);
+        evicted.exit_requested_at = now;
+        self.add_juror(juror)
+    }
+    
+    // Draws JURORS_PER_DISPUTE distinct jurors, skipping the parties to the dispute
+    pub fn select_jurors(&self, seed: &[u8; 32], excluded: &[Pubkey]) -> Result<[Pubkey; JURORS_PER_DISPUTE]> {
+        let mut candidates: Vec<Pubkey> = self.pool().iter().filter(|key| !excluded.contains(key)).copied().collect();
+        require!(candidates.len() >= JURORS_PER_DISPUTE, ViralSyncError::JurorPoolTooSmall);
+        
+        let mut selected = [Pubkey::default(); JURORS_PER_DISPUTE];
+        for (round, slot) in selected.iter_mut().enumerate() {
+            let draw = hashv(&[seed, &[round as u8]]).to_bytes();
+            let index = u64::from_le_bytes(draw[..8].try_into().unwrap()) % candidates.len() as u64;
+            *slot = candidates.swap_remove(index as usize);
+        }
+        Ok(selected)
+    }
 }
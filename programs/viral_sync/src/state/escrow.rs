@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+// Upper bound on wallets a single link drop can serve; accounts are sized to the requested count
+pub const MAX_LINK_DROP_CLAIMERS: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EscrowTerms {
+    pub expires_at: i64,
+    pub recipient: Option<Pubkey>,
+    pub claim_code_hash: Option<[u8; 32]>,
+    pub max_claimers: u16,       // 0 = single escrow, claimable in partial amounts
+    pub amount_per_claim: u64,   // Fixed slice per wallet when max_claimers > 0
+}
+
+// Token-holding escrow for a shared link. The escrow PDA owns both the escrow ATA and the escrow
+// TokenGeneration ([gen_v4, mint, escrow]), so only this program can release the tokens.
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub creator: Pubkey,         // Original sender; credited as referrer on every claim
+    pub nonce: u64,              // Lets one creator hold several open escrows (PDA seed)
+    pub amount: u64,             // Total deposited
+    pub claimed: u64,            // Released to claimants so far
+    
+    // Creator buckets the deposit was debited from; a harvest restores only what is unclaimed
+    pub deposited_gen1: u64,
+    pub deposited_gen2: u64,
+    pub deposited_dead: u64,
+    pub claim_count: u32,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub recipient: Option<Pubkey>,           // Claimable by this wallet's signature
+    pub claim_code_hash: Option<[u8; 32]>,   // Claimable by anyone presenting sha256 preimage
+    
+    // Link drops: one shared link, many wallets each claiming a fixed slice once
+    pub max_claimers: u16,
+    pub amount_per_claim: u64,
+    #[max_len(MAX_LINK_DROP_CLAIMERS)]
+    pub claimers: Vec<Pubkey>,
+}
+
+impl Escrow {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn space_for(max_claimers: u16) -> usize {
+        let claimers = (max_claimers as usize).min(MAX_LINK_DROP_CLAIMERS);
+        Self::LEN - 32 * MAX_LINK_DROP_CLAIMERS + 32 * claimers
+    }
+    
+    pub fn is_link_drop(&self) -> bool {
+        self.max_claimers > 0
+    }
+    
+    pub fn remaining(&self) -> u64 {
+        self.amount.saturating_sub(self.claimed)
+    }
+    
+    // Splits `returned` back into (gen1, gen2, dead). Claims and transfer fees are charged to gen1
+    // first, mirroring the hook's FIFO debit, so unshared dead tokens never come back as gen1.
+    pub fn unclaimed_split(&self, returned: u64) -> (u64, u64, u64) {
+        let deposited = self.deposited_gen1.saturating_add(self.deposited_gen2).saturating_add(self.deposited_dead);
+        let mut consumed = deposited.saturating_sub(returned);
+        let mut restore = |bucket: u64| {
+            let used = consumed.min(bucket);
+            consumed -= used;
+            bucket - used
+        };
+        let gen1 = restore(self.deposited_gen1);
+        let gen2 = restore(self.deposited_gen2);
+        let dead = restore(self.deposited_dead);
+        // Tokens sent to the escrow outside a deposit carry no attribution
+        (gen1, gen2, dead.saturating_add(returned.saturating_sub(deposited)))
+    }
+    
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+    
+    pub fn can_claim(&self, claimant: &Pubkey, claim_code: Option<&[u8]>) -> bool {
+        let by_signature = self.recipient == Some(*claimant);
+        let by_code = match (self.claim_code_hash, claim_code) {
+            (Some(expected), Some(code)) => hash(code).to_bytes() == expected,
+            _ => false,
+        };
+        by_signature || by_code
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+// Provider ids index directly into the registry (and into merchant allow-lists)
+pub const MAX_IDENTITY_PROVIDERS: usize = 16;
+
+// Domain separator for the provider-signed attestation: prefix || commitment || wallet
+pub const IDENTITY_ATTESTATION_PREFIX: &[u8] = b"viral_sync:identity:v1";
+
+#[account]
+#[derive(InitSpace)]
+pub struct IdentityRegistry {
+    pub bump: u8,
+    pub admin: Pubkey,
+    pub providers: [Pubkey; MAX_IDENTITY_PROVIDERS], // Pubkey::default() = unregistered slot
+}
+
+impl IdentityRegistry {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn provider_key(&self, provider_id: u16) -> Option<Pubkey> {
+        self.providers
+            .get(provider_id as usize)
+            .copied()
+            .filter(|key| *key != Pubkey::default())
+    }
+}
+
+// One per (mint, commitment). Never closed, so a commitment stays tied to its first wallet.
+#[account]
+#[derive(InitSpace)]
+pub struct IdentityClaim {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub commitment: [u8; 32],
+    pub owner: Pubkey,
+    pub provider_id: u16,
+    pub bound_at: i64,
+    pub is_bound: bool,
+}
+
+impl IdentityClaim {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::errors::ViralSyncError;
+
+pub const MAX_JUROR_POOL: usize = 64;
+pub const JURORS_PER_DISPUTE: usize = 5;
+pub const JUROR_QUORUM: usize = 3;
+pub const MIN_JUROR_STAKE_LAMPORTS: u64 = 1_000_000_000; // 1 SOL
+pub const JUROR_COMMIT_SECS: i64 = 259200; // 3 days
+pub const JUROR_REVEAL_SECS: i64 = 172800; // 2 days
+pub const JUROR_NO_SHOW_SLASH_BPS: u64 = 1000;
+pub const JUROR_EXIT_COOLDOWN_SECS: i64 = 604800; // 7 days, longer than a full commit-reveal round
+pub const JURY_DRAW_DELAY_SLOTS: u64 = 16; // summon_jurors commits to a slot this far ahead
+
+// SlotHashes sysvar: u64 entry count, then (slot: u64, hash: [u8; 32]) newest first
+const SLOT_HASH_ENTRY_LEN: usize = 40;
+
+// Hash of the first slot produced at or after `draw_slot`. Err until that slot has landed;
+// Ok(None) once it has aged out of the sysvar, so the draw has to be committed again.
+pub fn draw_slot_hash(slot_hashes: &[u8], draw_slot: u64) -> Result<Option<[u8; 32]>> {
+    require!(slot_hashes.len() >= 8, ErrorCode::AccountDidNotDeserialize);
+    let count = (u64::from_le_bytes(slot_hashes[..8].try_into().unwrap()) as usize)
+        .min((slot_hashes.len() - 8) / SLOT_HASH_ENTRY_LEN);
+    let entry = |index: usize| {
+        let start = 8 + index * SLOT_HASH_ENTRY_LEN;
+        let slot = u64::from_le_bytes(slot_hashes[start..start + 8].try_into().unwrap());
+        let hash: [u8; 32] = slot_hashes[start + 8..start + SLOT_HASH_ENTRY_LEN].try_into().unwrap();
+        (slot, hash)
+    };
+    
+    require!(count > 0 && entry(0).0 >= draw_slot, ViralSyncError::JuryDrawNotReady);
+    if entry(count - 1).0 > draw_slot {
+        return Ok(None);
+    }
+    Ok((0..count).rev().map(entry).find(|(slot, _)| *slot >= draw_slot).map(|(_, hash)| hash))
+}
+
+// sha256(uphold || salt || juror). Binding the juror stops a commitment being copied by another juror.
+pub fn vote_commitment(uphold: bool, salt: &[u8; 32], juror: &Pubkey) -> [u8; 32] {
+    hashv(&[&[uphold as u8], salt, juror.as_ref()]).to_bytes()
+}
+
+// One per juror. Lamports above rent are the stake; the account is closed on leaving the pool.
+#[account]
+#[derive(InitSpace)]
+pub struct JurorStake {
+    pub bump: u8,
+    pub juror: Pubkey,
+    pub stake_lamports: u64,
+    pub joined_at: i64,
+    pub active_disputes: u16, // Stake stays locked while this is non-zero
+    pub votes_revealed: u32,
+    pub no_shows: u32,
+    pub rewards_earned: u64,
+    pub exit_requested_at: i64, // 0 = still in the pool
+}
+
+impl JurorStake {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn slash_no_show(&mut self) -> u64 {
+        let slashed = self.stake_lamports.saturating_mul(JUROR_NO_SHOW_SLASH_BPS) / 10_000;
+        self.stake_lamports -= slashed;
+        self.no_shows = self.no_shows.saturating_add(1);
+        slashed
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VoteTally {
+    pub uphold: u8,
+    pub dismiss: u8,
+}
+
+impl VoteTally {
+    pub fn revealed(&self) -> usize {
+        (self.uphold + self.dismiss) as usize
+    }
+    
+    pub fn quorum_met(&self) -> bool {
+        self.revealed() >= JUROR_QUORUM
+    }
+    
+    // Ties dismiss: the watchdog carries the burden of proof
+    pub fn upheld(&self) -> bool {
+        self.uphold > self.dismiss
+    }
+}
+
+// Per-dispute jury ([dispute_vote, dispute]). Closed at tally so a vote without quorum can be re-summoned,
+// or by resolve_dispute if the arbiter rules before jurors are drawn.
+#[account]
+#[derive(InitSpace)]
+pub struct DisputeVote {
+    pub bump: u8,
+    pub dispute: Pubkey,
+    pub payer: Pubkey,
+    pub jurors: [Pubkey; JURORS_PER_DISPUTE],
+    pub commitments: [Option<[u8; 32]>; JURORS_PER_DISPUTE],
+    pub votes: [Option<bool>; JURORS_PER_DISPUTE], // Revealed: Some(true) = uphold
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub draw_slot: u64, // Jurors are drawn from this slot's hash; deadlines stay 0 until then
+}
+
+impl DisputeVote {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn is_drawn(&self) -> bool {
+        self.commit_deadline > 0
+    }
+    
+    fn seat(&self, juror: &Pubkey) -> Result<usize> {
+        self.jurors.iter().position(|key| key == juror).ok_or(ViralSyncError::NotSelectedJuror.into())
+    }
+    
+    pub fn commit(&mut self, juror: &Pubkey, commitment: [u8; 32], now: i64) -> Result<()> {
+        let seat = self.seat(juror)?;
+        require!(now <= self.commit_deadline, ViralSyncError::VotingPhaseClosed);
+        require!(self.commitments[seat].is_none(), ViralSyncError::VoteAlreadyCast);
+        self.commitments[seat] = Some(commitment);
+        Ok(())
+    }
+    
+    pub fn reveal(&mut self, juror: &Pubkey, uphold: bool, salt: &[u8; 32], now: i64) -> Result<()> {
+        let seat = self.seat(juror)?;
+        require!(now > self.commit_deadline && now <= self.reveal_deadline, ViralSyncError::VotingPhaseClosed);
+        require!(self.votes[seat].is_none(), ViralSyncError::VoteAlreadyCast);
+        require!(
+            self.commitments[seat] == Some(vote_commitment(uphold, salt, juror)),
+            ViralSyncError::InvalidVoteReveal
+        );
+        self.votes[seat] = Some(uphold);
+        Ok(())
+    }
+    
+    pub fn tally(&self) -> VoteTally {
+        let uphold = self.votes.iter().filter(|vote| **vote == Some(true)).count() as u8;
+        let dismiss = self.votes.iter().filter(|vote| **vote == Some(false)).count() as u8;
+        VoteTally { uphold, dismiss }
+    }
+    
+    pub fn can_tally(&self, now: i64) -> bool {
+        now > self.reveal_deadline || self.tally().revealed() == JURORS_PER_DISPUTE
+    }
+}
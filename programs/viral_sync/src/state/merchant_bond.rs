@@ -1,6 +1,14 @@
 use anchor_lang::prelude::*;
+use crate::errors::ViralSyncError;
+
+// Withdrawals wait this long after request_bond_unlock so watchdogs can still dispute
+pub const BOND_UNLOCK_DELAY_SECS: i64 = 172800; // 48 Hours
+
+// Protocol floor every bond keeps until the merchant is closed out
+pub const MIN_MERCHANT_BOND_LAMPORTS: u64 = 1_000_000_000; // 1 SOL
 
 #[account]
+#[derive(InitSpace)]
 pub struct MerchantBond {
     pub bump: u8,
     pub merchant: Pubkey,
@@ -8,4 +16,75 @@ pub struct MerchantBond {
     pub min_required_lamports: u64,
     pub is_locked: bool,
     pub unlock_requested_at: i64,
+    pub vault_bump: u8, // BondVault PDA holding the bonded lamports
+    
+    // Sunset payouts to token holders
+    pub redeemed_lamports: u64,
+    pub redeemed_tokens: u64,
+    
+    pub mint: Pubkey, // One bond per MerchantConfig (PDA seed), so each mint's holders are backed separately
+}
+
+impl MerchantBond {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    // Referral momentum on a mint only counts toward PoI while its merchant holds at least the floor
+    pub fn backs_poi(&self) -> bool {
+        self.bonded_lamports >= MIN_MERCHANT_BOND_LAMPORTS
+    }
+    
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        self.bonded_lamports = self.bonded_lamports.checked_add(amount).ok_or(ViralSyncError::MathOverflow)?;
+        Ok(())
+    }
+    
+    pub fn request_unlock(&mut self, now: i64) {
+        self.is_locked = false;
+        self.unlock_requested_at = now;
+    }
+    
+    // Each unlock request covers one withdrawal; the bond re-locks afterwards
+    pub fn withdraw(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(!self.is_locked, ViralSyncError::BondLocked);
+        require!(
+            now >= self.unlock_requested_at.saturating_add(BOND_UNLOCK_DELAY_SECS),
+            ViralSyncError::BondTimelockActive
+        );
+        let remaining = self.bonded_lamports.checked_sub(amount).ok_or(ViralSyncError::InsufficientBalance)?;
+        require!(
+            remaining >= self.min_required_lamports.max(MIN_MERCHANT_BOND_LAMPORTS),
+            ViralSyncError::InsufficientBalance
+        );
+        
+        self.bonded_lamports = remaining;
+        self.is_locked = true;
+        Ok(())
+    }
+    
+    // Lamports owed for `tokens` out of the `remaining_supply` still outstanding
+    pub fn pro_rata_share(&self, tokens: u64, remaining_supply: u64) -> u64 {
+        if remaining_supply == 0 {
+            return 0;
+        }
+        ((self.bonded_lamports as u128) * (tokens.min(remaining_supply) as u128) / (remaining_supply as u128)) as u64
+    }
+    
+    pub fn record_share_redemption(&mut self, lamports: u64, tokens: u64) -> Result<()> {
+        self.bonded_lamports = self.bonded_lamports.checked_sub(lamports).ok_or(ViralSyncError::InsufficientBalance)?;
+        self.redeemed_lamports = self.redeemed_lamports.saturating_add(lamports);
+        self.redeemed_tokens = self.redeemed_tokens.saturating_add(tokens);
+        Ok(())
+    }
+}
+
+// Program-owned lamport vault. Everything above its rent-exempt floor is bond.
+#[account]
+#[derive(InitSpace)]
+pub struct BondVault {
+    pub bump: u8,
+    pub merchant_bond: Pubkey,
+}
+
+impl BondVault {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
 }
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
+use crate::state::token_generation::TokenGeneration;
+use crate::errors::ViralSyncError;
 
 #[account]
+#[derive(InitSpace)]
 pub struct MerchantConfig {
     pub bump: u8,
     pub merchant: Pubkey,
@@ -25,9 +28,39 @@ pub struct MerchantConfig {
     
     pub close_initiated_at: i64,
     pub close_window_ends_at: i64,
+    
+    // Referrers below this Proof-of-Influence score earn no commission (0 disables)
+    pub min_poi_score_for_commission: u32,
+    
+    // Sybil resistance: unmet identity requirements degrade attribution to DeadPass
+    pub require_identity_for_referrer: bool,
+    pub require_identity_for_referred: bool,
+    pub allowed_identity_providers: u16, // Bit i allows provider id i; 0 = any registered provider
+}
+
+impl MerchantConfig {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn referrer_identity_ok(&self, referrer: &TokenGeneration) -> bool {
+        !self.require_identity_for_referrer || referrer.has_identity_from(self.allowed_identity_providers)
+    }
+    
+    pub fn referred_identity_ok(&self, referred: &TokenGeneration) -> bool {
+        !self.require_identity_for_referred || referred.has_identity_from(self.allowed_identity_providers)
+    }
+    
+    // Per-referral anti-sybil limits. Peer shares pay them in the hook; escrows pay them once per
+    // claim, since a single deposit can fan out into many referrals.
+    pub fn enforce_referral_limits(&self, referrer: &mut TokenGeneration, amount: u64, slot: u64) -> Result<()> {
+        require!(amount >= self.min_tokens_per_referral, ViralSyncError::BelowMinimum);
+        require!(amount <= self.max_tokens_per_referral, ViralSyncError::ExceedsMaximum);
+        let today_index = slot / self.slots_per_day.max(1);
+        referrer.consume_daily_share(today_index, self.max_referrals_per_wallet_per_day)
+    }
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct VaultEntry {
     pub bump: u8,
     pub vault: Pubkey,
@@ -36,7 +69,12 @@ pub struct VaultEntry {
     pub is_dex: bool, // Support for registering DEX pools as DEX endpoints
 }
 
+impl VaultEntry {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
+
 #[account]
+#[derive(InitSpace)]
 pub struct GeoFence {
     pub bump: u8,
     pub vault: Pubkey,
@@ -53,3 +91,7 @@ pub struct GeoFence {
     pub allow_non_geo_redemption: bool,
     pub non_geo_commission_penalty_bps: u16,
 }
+
+impl GeoFence {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 #[account]
+#[derive(InitSpace)]
 pub struct MerchantReputation {
     pub bump: u8,
     pub merchant: Pubkey,
@@ -19,3 +20,7 @@ pub struct MerchantReputation {
     pub suspicion_score: u32,         // Flag mark for auto-disputes/warnings
     pub suspicion_computed_at: i64,   // Last Oracle pipeline update
 }
+
+impl MerchantReputation {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
@@ -7,6 +7,10 @@ pub mod merchant_bond;
 pub mod merchant_reputation;
 pub mod viral_oracle;
 pub mod session_key;
+pub mod identity;
+pub mod escrow;
+pub mod jury;
+pub mod oracle_authority;
 
 pub use merchant_config::*;
 pub use token_generation::*;
@@ -17,3 +21,7 @@ pub use merchant_bond::*;
 pub use merchant_reputation::*;
 pub use viral_oracle::*;
 pub use session_key::*;
+pub use identity::*;
+pub use escrow::*;
+pub use jury::*;
+pub use oracle_authority::*;
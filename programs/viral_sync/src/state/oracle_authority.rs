@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::errors::ViralSyncError;
+
+pub const MAX_ORACLE_SIGNERS: usize = 8;
+
+// Protocol-wide registry of keys allowed to publish oracle data ([oracle_authority]).
+// Any one signer may update a viral oracle; reputation updates need `reputation_threshold` of them.
+#[account]
+#[derive(InitSpace)]
+pub struct OracleAuthority {
+    pub bump: u8,
+    pub admin: Pubkey,
+    
+    // The first `signer_count` entries are live
+    pub signer_count: u8,
+    pub signers: [Pubkey; MAX_ORACLE_SIGNERS],
+    pub reputation_threshold: u8,
+}
+
+impl OracleAuthority {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn signers(&self) -> &[Pubkey] {
+        &self.signers[..self.signer_count as usize]
+    }
+    
+    pub fn is_signer(&self, key: &Pubkey) -> bool {
+        self.signers().contains(key)
+    }
+    
+    pub fn add_signer(&mut self, signer: Pubkey) -> Result<()> {
+        require!(!self.is_signer(&signer), ViralSyncError::OracleSignerAlreadyRegistered);
+        require!((self.signer_count as usize) < MAX_ORACLE_SIGNERS, ViralSyncError::OracleRegistryFull);
+        self.signers[self.signer_count as usize] = signer;
+        self.signer_count += 1;
+        Ok(())
+    }
+    
+    // Swap-remove; the threshold must stay reachable, so lower it first if needed
+    pub fn remove_signer(&mut self, signer: &Pubkey) -> Result<()> {
+        let index = self.signers().iter().position(|key| key == signer).ok_or(ViralSyncError::UnauthorizedOracle)?;
+        require!(self.signer_count > self.reputation_threshold, ViralSyncError::InvalidOracleThreshold);
+        let last = self.signer_count as usize - 1;
+        self.signers[index] = self.signers[last];
+        self.signers[last] = Pubkey::default();
+        self.signer_count -= 1;
+        Ok(())
+    }
+    
+    pub fn set_reputation_threshold(&mut self, threshold: u8) -> Result<()> {
+        require!(threshold > 0 && threshold <= self.signer_count, ViralSyncError::InvalidOracleThreshold);
+        self.reputation_threshold = threshold;
+        Ok(())
+    }
+    
+    // Distinct registered keys among `signers`; duplicates and unknown keys count for nothing
+    pub fn count_approvals<'a>(&self, signers: impl Iterator<Item = &'a Pubkey>) -> u8 {
+        let mut approved: Vec<&Pubkey> = signers.filter(|key| self.is_signer(key)).collect();
+        approved.sort();
+        approved.dedup();
+        approved.len() as u8
+    }
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 #[account]
+#[derive(InitSpace)]
 pub struct ReferralRecord {
     pub bump: u8,
     pub merchant: Pubkey,
@@ -20,6 +21,8 @@ pub struct ReferralRecord {
 }
 
 impl ReferralRecord {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
     pub fn is_expired(&self, now: i64) -> bool {
         self.expires_at > 0 && now > self.expires_at
     }
@@ -1,6 +1,20 @@
 use anchor_lang::prelude::*;
+use crate::errors::ViralSyncError;
+
+// Permission bits a session delegate may be granted. Claim and finalize are owner-signed; their
+// bits gate the delegate-signed claim_commission_with_session and finalize_inbound_with_session.
+pub const SESSION_PERMISSION_SHARE: u8 = 1 << 0;
+pub const SESSION_PERMISSION_REDEEM: u8 = 1 << 1;
+pub const SESSION_PERMISSION_BURN: u8 = 1 << 2;
+pub const SESSION_PERMISSION_CLAIM_COMMISSION: u8 = 1 << 3;
+pub const SESSION_PERMISSION_FINALIZE: u8 = 1 << 4;
+pub const SESSION_PERMISSION_ALL: u8 = 0b1_1111;
+
+pub const MAX_SESSION_DESTINATIONS: usize = 4;
+pub const SESSION_DAY_SECS: i64 = 86400;
 
 #[account]
+#[derive(InitSpace)]
 pub struct SessionKey {
     pub bump: u8,
     pub authority: Pubkey,       // User's main wallet
@@ -10,10 +24,73 @@ pub struct SessionKey {
     pub max_tokens_per_session: u64, // Spend limit boundary
     pub tokens_spent: u64,       // Active consumption tracking
     pub is_active: bool,
+    
+    // Scoping
+    pub permissions: u8,         // SESSION_PERMISSION_* bitmask
+    pub daily_spend_limit: u64,  // 0 = only the session-wide cap applies
+    pub spend_day: i64,          // unix_timestamp / SESSION_DAY_SECS of the current window
+    pub spent_today: u64,
+    pub allowed_destination_count: u8, // 0 = any destination
+    pub allowed_destinations: [Pubkey; MAX_SESSION_DESTINATIONS], // Destination token account owners
+    
+    pub epoch: u32,              // TokenGeneration.session_epoch at creation (also a PDA seed)
 }
 
 impl SessionKey {
-    pub fn is_valid(&self, now: i64) -> bool {
-        self.is_active && self.expires_at > now
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn is_valid(&self, now: i64, current_epoch: u32) -> bool {
+        self.is_active && self.expires_at > now && self.epoch == current_epoch
+    }
+    
+    pub fn allows_destination(&self, destination_owner: &Pubkey) -> bool {
+        let count = (self.allowed_destination_count as usize).min(MAX_SESSION_DESTINATIONS);
+        count == 0 || self.allowed_destinations[..count].contains(destination_owner)
+    }
+    
+    // Escrow shares are checked against who may claim them, not the escrow PDA holding the tokens.
+    // Claim-code escrows pay whoever holds the code, so an allow-listed session cannot fund them.
+    pub fn allows_escrow_recipient(&self, recipient: Option<Pubkey>) -> bool {
+        match recipient {
+            Some(recipient) => self.allows_destination(&recipient),
+            None => self.allowed_destination_count == 0,
+        }
+    }
+    
+    // Every delegate-signed instruction funnels through here before moving tokens
+    pub fn authorize_spend(
+        &mut self,
+        generation: Pubkey,
+        current_epoch: u32,
+        permission: u8,
+        amount: u64,
+        destination_owner: Option<Pubkey>,
+        now: i64,
+    ) -> Result<()> {
+        require!(self.is_valid(now, current_epoch), ViralSyncError::SessionKeyInvalid);
+        require!(self.target_generation == generation, ViralSyncError::SessionGenerationMismatch);
+        require!(self.permissions & permission == permission, ViralSyncError::SessionPermissionDenied);
+        
+        if let Some(destination) = destination_owner {
+            require!(self.allows_destination(&destination), ViralSyncError::SessionDestinationNotAllowed);
+        }
+        
+        let spent = self.tokens_spent.checked_add(amount).ok_or(ViralSyncError::MathOverflow)?;
+        require!(spent <= self.max_tokens_per_session, ViralSyncError::SessionSpendCapExceeded);
+        
+        let today = now / SESSION_DAY_SECS;
+        if self.spend_day != today {
+            self.spend_day = today;
+            self.spent_today = 0;
+        }
+        let spent_today = self.spent_today.checked_add(amount).ok_or(ViralSyncError::MathOverflow)?;
+        if self.daily_spend_limit > 0 {
+            require!(spent_today <= self.daily_spend_limit, ViralSyncError::SessionDailyLimitExceeded);
+        }
+        
+        self.tokens_spent = spent;
+        self.spent_today = spent_today;
+        
+        Ok(())
     }
 }
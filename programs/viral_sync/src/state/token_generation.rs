@@ -1,8 +1,20 @@
 use anchor_lang::prelude::*;
+use crate::errors::ViralSyncError;
 
 pub const INBOUND_BUFFER_SIZE: usize = 16;
 
+// Layout version. Fields added after v4 are appended, so migrate_token_generation only zero-extends.
+pub const TOKEN_GENERATION_VERSION: u8 = 5;
+pub const TOKEN_GENERATION_VERSION_OFFSET: usize = 9; // Discriminator, then bump
+
+// Proof of Influence: each redemption-driven referral adds up to this many points, weighted by
+// the tokens redeemed; points halve every 30 days
+pub const POI_POINTS_PER_REFERRAL: u64 = 100;
+pub const POI_HALF_LIFE_SECS: i64 = 2592000;
+pub const MAX_POI_GENERATIONS: usize = 16;
+
 #[account]
+#[derive(InitSpace)]
 pub struct TokenGeneration {
     pub bump: u8,
     pub version: u8,
@@ -49,15 +61,116 @@ pub struct TokenGeneration {
     pub is_dex_pool: bool,
     
     // Proof of Influence
-    pub poi_score: u32,
+    pub poi_score: u32,              // Cross-merchant aggregate, written by compute_poi_score
     pub poi_updated_at: i64,
     
     // Identity extensions
     pub identity_commitment: Option<[u8; 32]>,
     pub identity_provider: u16,
+    
+    // ── v5 ──
+    // Escrow PDA seeds, checked by the hook before honouring is_intermediary
+    pub escrow_creator: Pubkey,
+    pub escrow_nonce: u64,
+    pub escrow_bump: u8,
+    
+    // Activity summary (read by wallets and Proof-of-Influence scoring)
+    pub total_shared: u64,           // Tokens sent out through peer/escrow shares
+    pub total_redeemed: u64,         // Tokens redeemed at registered vaults
+    pub total_commission_claimed: u64,
+    pub share_count: u32,
+    pub redemption_count: u32,
+    pub claim_count: u32,
+    pub successful_referrals: u32,   // Referred wallets whose redemptions paid this wallet commission
+    
+    pub session_epoch: u32, // Bumped by revoke_all_sessions; older SessionKeys stop validating
+    
+    pub poi_momentum: u64,           // This mint's decaying referral points
+    pub poi_momentum_at: i64,
+}
+
+impl TokenGeneration {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn record_received(&mut self, amount: u64) {
+        self.total_lifetime = self.total_lifetime.saturating_add(amount);
+    }
+    
+    pub fn record_share(&mut self, amount: u64) {
+        self.total_shared = self.total_shared.saturating_add(amount);
+        self.share_count = self.share_count.saturating_add(1);
+    }
+    
+    pub fn record_redemption(&mut self, amount: u64) {
+        self.total_redeemed = self.total_redeemed.saturating_add(amount);
+        self.redemption_count = self.redemption_count.saturating_add(1);
+    }
+    
+    pub fn record_commission_claim(&mut self, amount: u64) {
+        self.total_commission_claimed = self.total_commission_claimed.saturating_add(amount);
+        self.claim_count = self.claim_count.saturating_add(1);
+    }
+    
+    // Only a generation owned by the escrow PDA it was opened for counts as an intermediary
+    pub fn is_valid_intermediary(&self) -> bool {
+        let nonce = self.escrow_nonce.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"escrow", self.mint.as_ref(), self.escrow_creator.as_ref(), &nonce, &[self.escrow_bump]];
+        self.is_intermediary
+            && Pubkey::create_program_address(seeds, &crate::ID).is_ok_and(|escrow| escrow == self.owner)
+    }
+    
+    // Counts one referral against the merchant's per-wallet daily cap
+    pub fn consume_daily_share(&mut self, today_index: u64, max_per_day: u16) -> Result<()> {
+        if self.share_limit_day == today_index {
+            require!(self.shares_today < max_per_day, ViralSyncError::DailyShareLimitExceeded);
+            self.shares_today += 1;
+        } else {
+            self.share_limit_day = today_index;
+            self.shares_today = 1;
+        }
+        Ok(())
+    }
+    
+    // Redeeming a full referral's worth (`full_weight` tokens) earns the full points; dust earns next to none
+    pub fn accrue_poi(&mut self, now: i64, redeemed: u64, full_weight: u64) {
+        let points = (POI_POINTS_PER_REFERRAL as u128) * (redeemed.min(full_weight) as u128) / (full_weight.max(1) as u128);
+        self.poi_momentum = self.poi_momentum_at(now).saturating_add(points as u64);
+        self.poi_momentum_at = now;
+    }
+    
+    pub fn poi_momentum_at(&self, now: i64) -> u64 {
+        decay_poi(self.poi_momentum, now.saturating_sub(self.poi_momentum_at))
+    }
+    
+    // `allowed_providers` is a bitmask over provider ids; 0 accepts any registered provider
+    pub fn has_identity_from(&self, allowed_providers: u16) -> bool {
+        self.identity_commitment.is_some()
+            && (allowed_providers == 0
+                || (self.identity_provider < 16 && allowed_providers & (1 << self.identity_provider) != 0))
+    }
+    
+    // A stale aggregate keeps decaying, so skipping compute_poi_score never helps a wallet
+    pub fn effective_poi_score(&self, now: i64) -> u32 {
+        decay_poi(self.poi_score as u64, now.saturating_sub(self.poi_updated_at)) as u32
+    }
+}
+
+/// Halves `value` per elapsed half-life, interpolating linearly inside the current one.
+pub fn decay_poi(value: u64, elapsed_secs: i64) -> u64 {
+    if elapsed_secs <= 0 || value == 0 {
+        return value;
+    }
+    let half_lives = elapsed_secs / POI_HALF_LIFE_SECS;
+    if half_lives >= 64 {
+        return 0;
+    }
+    let halved = value >> half_lives;
+    let into_period = (elapsed_secs % POI_HALF_LIFE_SECS) as u128;
+    let partial = (halved as u128) * into_period / (2 * POI_HALF_LIFE_SECS as u128);
+    halved - partial as u64
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default, Copy)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default, Copy, InitSpace)]
 pub enum GenSource {
     #[default]
     DeadPass,
@@ -65,7 +178,7 @@ pub enum GenSource {
     Issuance,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
 pub struct InboundEntry {
     pub referrer: Pubkey,
     pub amount: u64,
@@ -75,7 +188,7 @@ pub struct InboundEntry {
     pub _padding: [u8; 7],
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
 pub struct ReferrerSlot {
     pub referrer: Pubkey,
     pub referral_record: Pubkey,
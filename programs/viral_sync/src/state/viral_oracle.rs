@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 #[account]
+#[derive(InitSpace)]
 pub struct ViralOracle {
     pub bump: u8,
     pub merchant: Pubkey,
@@ -32,3 +33,7 @@ pub struct ViralOracle {
     pub computed_at: i64,
     pub data_points: u32,
 }
+
+impl ViralOracle {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
@@ -0,0 +1,441 @@
+// Serializes maximal instances of every account and checks they fit the `LEN` used by `init`.
+// Struct literals are spelled out on purpose: adding a field breaks this file until it is covered.
+
+use anchor_lang::prelude::*;
+use viral_sync::state::*;
+
+const KEY: Pubkey = Pubkey::new_from_array([0xFF; 32]);
+
+fn serialized_len<T: AccountSerialize>(account: &T) -> usize {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.len()
+}
+
+fn assert_fits<T: AccountSerialize>(name: &str, account: &T, len: usize) {
+    let used = serialized_len(account);
+    assert!(used <= len, "{name}: serialized {used} bytes but LEN is {len}");
+}
+
+fn max_token_generation() -> TokenGeneration {
+    TokenGeneration {
+        bump: u8::MAX,
+        version: u8::MAX,
+        mint: KEY,
+        owner: KEY,
+        gen1_balance: u64::MAX,
+        gen2_balance: u64::MAX,
+        dead_balance: u64::MAX,
+        total_lifetime: u64::MAX,
+        is_intermediary: true,
+        original_sender: KEY,
+        inbound_buffer: [InboundEntry {
+            referrer: KEY,
+            amount: u64::MAX,
+            generation_source: GenSource::Issuance,
+            slot: u64::MAX,
+            processed: true,
+            _padding: [u8::MAX; 7],
+        }; INBOUND_BUFFER_SIZE],
+        buffer_head: u8::MAX,
+        buffer_pending: u8::MAX,
+        referrer_slots: [ReferrerSlot {
+            referrer: KEY,
+            referral_record: KEY,
+            tokens_attributed: u64::MAX,
+            tokens_redeemed_so_far: u64::MAX,
+            is_active: true,
+        }; 4],
+        active_referrer_slots: u8::MAX,
+        first_received_at: i64::MAX,
+        last_received_at: i64::MAX,
+        share_limit_day: u64::MAX,
+        shares_today: u16::MAX,
+        processing_nonce: u64::MAX,
+        redemption_pending: true,
+        redemption_slot: u64::MAX,
+        redemption_gen2_consumed: u64::MAX,
+        redemption_slot_consumed: [u64::MAX; 4],
+        redemption_slots_settled: u8::MAX,
+        is_treasury: true,
+        is_dex_pool: true,
+        poi_score: u32::MAX,
+        poi_updated_at: i64::MAX,
+        identity_commitment: Some([u8::MAX; 32]),
+        identity_provider: u16::MAX,
+        escrow_creator: KEY,
+        escrow_nonce: u64::MAX,
+        escrow_bump: u8::MAX,
+        total_shared: u64::MAX,
+        total_redeemed: u64::MAX,
+        total_commission_claimed: u64::MAX,
+        share_count: u32::MAX,
+        redemption_count: u32::MAX,
+        claim_count: u32::MAX,
+        successful_referrals: u32::MAX,
+        session_epoch: u32::MAX,
+        poi_momentum: u64::MAX,
+        poi_momentum_at: i64::MAX,
+    }
+}
+
+#[test]
+fn token_generation_fits() {
+    assert_fits("TokenGeneration", &max_token_generation(), TokenGeneration::LEN);
+}
+
+// v4 accounts are migrated by zero-extension, so v5 fields must sit after the whole v4 layout
+#[test]
+fn token_generation_v5_fields_are_appended() {
+    const V5_BYTES: usize = 32 + 8 + 1 + 3 * 8 + 4 * 4 + 4 + 8 + 8;
+    let mut gen = max_token_generation();
+    gen.version = TOKEN_GENERATION_VERSION;
+    let mut v4 = gen.clone();
+    v4.escrow_creator = Pubkey::default();
+    v4.escrow_nonce = 0;
+    v4.escrow_bump = 0;
+    v4.total_shared = 0;
+    v4.total_redeemed = 0;
+    v4.total_commission_claimed = 0;
+    v4.share_count = 0;
+    v4.redemption_count = 0;
+    v4.claim_count = 0;
+    v4.successful_referrals = 0;
+    v4.session_epoch = 0;
+    v4.poi_momentum = 0;
+    v4.poi_momentum_at = 0;
+    
+    let mut full = Vec::new();
+    gen.try_serialize(&mut full).unwrap();
+    let mut zero_extended = Vec::new();
+    v4.try_serialize(&mut zero_extended).unwrap();
+    let prefix = full.len() - V5_BYTES;
+    assert_eq!(full[..prefix], zero_extended[..prefix]);
+    assert!(zero_extended[prefix..].iter().all(|byte| *byte == 0));
+    assert_eq!(full[TOKEN_GENERATION_VERSION_OFFSET], TOKEN_GENERATION_VERSION);
+}
+
+#[test]
+fn merchant_config_fits() {
+    let config = MerchantConfig {
+        bump: u8::MAX,
+        merchant: KEY,
+        mint: KEY,
+        is_active: true,
+        min_hold_before_share_secs: i64::MAX,
+        min_tokens_per_referral: u64::MAX,
+        max_tokens_per_referral: u64::MAX,
+        max_referrals_per_wallet_per_day: u16::MAX,
+        allow_second_gen_transfer: true,
+        slots_per_day: u64::MAX,
+        token_expiry_days: u16::MAX,
+        commission_rate_bps: u16::MAX,
+        transfer_fee_bps: u16::MAX,
+        first_issuance_done: true,
+        current_supply: u64::MAX,
+        tokens_issued: u64::MAX,
+        close_initiated_at: i64::MAX,
+        close_window_ends_at: i64::MAX,
+        min_poi_score_for_commission: u32::MAX,
+        require_identity_for_referrer: true,
+        require_identity_for_referred: true,
+        allowed_identity_providers: u16::MAX,
+    };
+    assert_fits("MerchantConfig", &config, MerchantConfig::LEN);
+}
+
+#[test]
+fn vault_entry_and_geo_fence_fit() {
+    let vault = VaultEntry {
+        bump: u8::MAX,
+        vault: KEY,
+        merchant: KEY,
+        is_active: true,
+        is_dex: true,
+    };
+    assert_fits("VaultEntry", &vault, VaultEntry::LEN);
+    
+    let fence = GeoFence {
+        bump: u8::MAX,
+        vault: KEY,
+        merchant: KEY,
+        lat_micro: i32::MIN,
+        lng_micro: i32::MIN,
+        radius_meters: u32::MAX,
+        is_active: true,
+        attestation_server_count: u8::MAX,
+        attestation_servers: [KEY; 4],
+        allow_non_geo_redemption: true,
+        non_geo_commission_penalty_bps: u16::MAX,
+    };
+    assert_fits("GeoFence", &fence, GeoFence::LEN);
+}
+
+#[test]
+fn commission_and_referral_accounts_fit() {
+    let ledger = CommissionLedger {
+        bump: u8::MAX,
+        referrer: KEY,
+        merchant: KEY,
+        mint: KEY,
+        claimable: u64::MAX,
+        dust_tenths_accumulated: u32::MAX,
+        frozen: true,
+        frozen_amount: u64::MAX,
+        total_earned: u64::MAX,
+        total_claimed: u64::MAX,
+        total_redemptions_driven: u64::MAX,
+        highest_single_commission: u64::MAX,
+    };
+    assert_fits("CommissionLedger", &ledger, CommissionLedger::LEN);
+    
+    let referral = ReferralRecord {
+        bump: u8::MAX,
+        merchant: KEY,
+        mint: KEY,
+        referrer: KEY,
+        referred: KEY,
+        created_at: i64::MAX,
+        expires_at: i64::MAX,
+        committed_commission_bps: u16::MAX,
+        max_commission_cap: u64::MAX,
+        commission_earned: u64::MAX,
+        commission_settled: u64::MAX,
+        is_active: true,
+    };
+    assert_fits("ReferralRecord", &referral, ReferralRecord::LEN);
+}
+
+#[test]
+fn dispute_record_fits() {
+    let dispute = DisputeRecord {
+        bump: u8::MAX,
+        merchant: KEY,
+        referral: KEY,
+        watchdog: KEY,
+        status: DisputeStatus::UpheldByVote,
+        stake_lamports: u64::MAX,
+        raised_at: i64::MAX,
+        resolved_at: Some(i64::MAX),
+        evidence_hash: Some([u8::MAX; 32]),
+        responded_at: Some(i64::MAX),
+        disputed_amount: u64::MAX,
+        escrow_bump: u8::MAX,
+    };
+    assert_fits("DisputeRecord", &dispute, DisputeRecord::LEN);
+    
+    let escrow = DisputeEscrow {
+        bump: u8::MAX,
+        dispute: KEY,
+    };
+    assert_fits("DisputeEscrow", &escrow, DisputeEscrow::LEN);
+    
+    let arbitration = Arbitration {
+        bump: u8::MAX,
+        admin: KEY,
+        arbiter: KEY,
+        juror_count: u8::MAX,
+        jurors: [KEY; MAX_JUROR_POOL],
+    };
+    assert_fits("Arbitration", &arbitration, Arbitration::LEN);
+}
+
+#[test]
+fn oracle_authority_fits() {
+    let authority = OracleAuthority {
+        bump: u8::MAX,
+        admin: KEY,
+        signer_count: u8::MAX,
+        signers: [KEY; MAX_ORACLE_SIGNERS],
+        reputation_threshold: u8::MAX,
+    };
+    assert_fits("OracleAuthority", &authority, OracleAuthority::LEN);
+}
+
+#[test]
+fn jury_accounts_fit() {
+    let stake = JurorStake {
+        bump: u8::MAX,
+        juror: KEY,
+        stake_lamports: u64::MAX,
+        joined_at: i64::MAX,
+        active_disputes: u16::MAX,
+        votes_revealed: u32::MAX,
+        no_shows: u32::MAX,
+        rewards_earned: u64::MAX,
+        exit_requested_at: i64::MAX,
+    };
+    assert_fits("JurorStake", &stake, JurorStake::LEN);
+    
+    let vote = DisputeVote {
+        bump: u8::MAX,
+        dispute: KEY,
+        payer: KEY,
+        jurors: [KEY; JURORS_PER_DISPUTE],
+        commitments: [Some([u8::MAX; 32]); JURORS_PER_DISPUTE],
+        votes: [Some(true); JURORS_PER_DISPUTE],
+        commit_deadline: i64::MAX,
+        reveal_deadline: i64::MAX,
+        draw_slot: u64::MAX,
+    };
+    assert_fits("DisputeVote", &vote, DisputeVote::LEN);
+}
+
+#[test]
+fn merchant_bond_and_reputation_fit() {
+    let bond = MerchantBond {
+        bump: u8::MAX,
+        merchant: KEY,
+        bonded_lamports: u64::MAX,
+        min_required_lamports: u64::MAX,
+        is_locked: true,
+        unlock_requested_at: i64::MAX,
+        vault_bump: u8::MAX,
+        redeemed_lamports: u64::MAX,
+        redeemed_tokens: u64::MAX,
+        mint: KEY,
+    };
+    assert_fits("MerchantBond", &bond, MerchantBond::LEN);
+    
+    let vault = BondVault {
+        bump: u8::MAX,
+        merchant_bond: KEY,
+    };
+    assert_fits("BondVault", &vault, BondVault::LEN);
+    
+    let reputation = MerchantReputation {
+        bump: u8::MAX,
+        merchant: KEY,
+        reputation_score: u32::MAX,
+        timeout_disputes: u32::MAX,
+        pct_redeemers_aged_over_30_days: u16::MAX,
+        unique_attestation_servers_used: u8::MAX,
+        commission_concentration_bps: u16::MAX,
+        pct_redemptions_in_business_hours: u16::MAX,
+        avg_poi_score_top_referrers: u32::MAX,
+        suspicion_score: u32::MAX,
+        suspicion_computed_at: i64::MAX,
+    };
+    assert_fits("MerchantReputation", &reputation, MerchantReputation::LEN);
+}
+
+#[test]
+fn viral_oracle_fits() {
+    let oracle = ViralOracle {
+        bump: u8::MAX,
+        merchant: KEY,
+        mint: KEY,
+        k_factor: u64::MAX,
+        median_referrals_per_user: u32::MAX,
+        p90_referrals_per_user: u32::MAX,
+        p10_referrals_per_user: u32::MAX,
+        referral_concentration_index: u32::MAX,
+        share_rate: u32::MAX,
+        claim_rate: u32::MAX,
+        first_redeem_rate: u32::MAX,
+        avg_time_share_to_claim_secs: u32::MAX,
+        avg_time_claim_to_redeem_secs: u32::MAX,
+        p50_time_share_to_claim_secs: u32::MAX,
+        commission_per_new_customer_tokens: u64::MAX,
+        vs_google_ads_efficiency_bps: u32::MAX,
+        computed_at: i64::MAX,
+        data_points: u32::MAX,
+    };
+    assert_fits("ViralOracle", &oracle, ViralOracle::LEN);
+}
+
+#[test]
+fn session_key_fits() {
+    let session = SessionKey {
+        bump: u8::MAX,
+        authority: KEY,
+        target_generation: KEY,
+        delegate: KEY,
+        expires_at: i64::MAX,
+        max_tokens_per_session: u64::MAX,
+        tokens_spent: u64::MAX,
+        is_active: true,
+        permissions: u8::MAX,
+        daily_spend_limit: u64::MAX,
+        spend_day: i64::MAX,
+        spent_today: u64::MAX,
+        allowed_destination_count: u8::MAX,
+        allowed_destinations: [KEY; MAX_SESSION_DESTINATIONS],
+        epoch: u32::MAX,
+    };
+    assert_fits("SessionKey", &session, SessionKey::LEN);
+}
+
+#[test]
+fn identity_accounts_fit() {
+    let registry = IdentityRegistry {
+        bump: u8::MAX,
+        admin: KEY,
+        providers: [KEY; MAX_IDENTITY_PROVIDERS],
+    };
+    assert_fits("IdentityRegistry", &registry, IdentityRegistry::LEN);
+    
+    let claim = IdentityClaim {
+        bump: u8::MAX,
+        mint: KEY,
+        commitment: [u8::MAX; 32],
+        owner: KEY,
+        provider_id: u16::MAX,
+        bound_at: i64::MAX,
+        is_bound: true,
+    };
+    assert_fits("IdentityClaim", &claim, IdentityClaim::LEN);
+}
+
+// Generations were historically allocated at 8 + 1700 bytes; derived sizing must stay within it
+const _: () = assert!(TokenGeneration::LEN <= 8 + 1700);
+
+#[test]
+fn escrow_fits() {
+    let escrow = Escrow {
+        bump: u8::MAX,
+        mint: KEY,
+        creator: KEY,
+        nonce: u64::MAX,
+        amount: u64::MAX,
+        claimed: u64::MAX,
+        deposited_gen1: u64::MAX,
+        deposited_gen2: u64::MAX,
+        deposited_dead: u64::MAX,
+        claim_count: u32::MAX,
+        created_at: i64::MAX,
+        expires_at: i64::MAX,
+        recipient: Some(KEY),
+        claim_code_hash: Some([u8::MAX; 32]),
+        max_claimers: u16::MAX,
+        amount_per_claim: u64::MAX,
+        claimers: vec![KEY; MAX_LINK_DROP_CLAIMERS],
+    };
+    assert_fits("Escrow", &escrow, Escrow::LEN);
+}
+
+#[test]
+fn link_drop_escrow_fits_its_requested_size() {
+    for max_claimers in [0u16, 1, 7, MAX_LINK_DROP_CLAIMERS as u16] {
+        let escrow = Escrow {
+            bump: u8::MAX,
+            mint: KEY,
+            creator: KEY,
+            nonce: u64::MAX,
+            amount: u64::MAX,
+            claimed: u64::MAX,
+            deposited_gen1: u64::MAX,
+            deposited_gen2: u64::MAX,
+            deposited_dead: u64::MAX,
+            claim_count: u32::MAX,
+            created_at: i64::MAX,
+            expires_at: i64::MAX,
+            recipient: Some(KEY),
+            claim_code_hash: Some([u8::MAX; 32]),
+            max_claimers,
+            amount_per_claim: u64::MAX,
+            claimers: vec![KEY; max_claimers as usize],
+        };
+        assert_fits("Escrow", &escrow, Escrow::space_for(max_claimers));
+    }
+}
@@ -0,0 +1,238 @@
+// Account fixtures shared by the integration suites that drive real account validation
+// and handlers off-chain. Suites that run handlers declare this module with #[macro_use].
+#![allow(dead_code, unused_macros)]
+
+use std::sync::Once;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::SUCCESS;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::system_program;
+use viral_sync::state::*;
+
+pub const NOW: i64 = 1_700_000_000;
+pub const NOW_SLOT: u64 = 250_000_000;
+
+// Clock::get() returns NOW at NOW_SLOT; CPIs fall through to the default stub and do nothing
+struct FixedClock;
+
+impl SyscallStubs for FixedClock {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock { slot: NOW_SLOT, unix_timestamp: NOW, ..Clock::default() };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+}
+
+pub fn install_clock() {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(FixedClock));
+    });
+}
+
+// Validates `$fixtures` as `$accounts`, then runs the handler and returns its result. On success
+// the accounts are written back into `$fixtures`. Structs that read instruction args take them
+// serialized via `@ix`.
+macro_rules! run_handler {
+    (@ix $ix_data:expr, $accounts:ty, $handler:path, $fixtures:ident $(, $arg:expr)*) => {{
+        crate::common::install_clock();
+        let infos = crate::common::infos(&mut $fixtures);
+        let mut remaining: &[::anchor_lang::prelude::AccountInfo] = &infos;
+        let mut bumps = Default::default();
+        let mut accounts = <$accounts as ::anchor_lang::Accounts<'_, _>>::try_accounts(
+            &viral_sync::ID, &mut remaining, $ix_data, &mut bumps, &mut ::std::collections::BTreeSet::new()
+        ).expect("fixtures must pass account validation");
+        let result = $handler(::anchor_lang::context::Context::new(&viral_sync::ID, &mut accounts, &[], bumps) $(, $arg)*);
+        if result.is_ok() {
+            ::anchor_lang::AccountsExit::exit(&accounts, &viral_sync::ID).unwrap();
+        }
+        result
+    }};
+    ($accounts:ty, $handler:path, $fixtures:ident $(, $arg:expr)*) => {
+        run_handler!(@ix &[], $accounts, $handler, $fixtures $(, $arg)*)
+    };
+}
+
+macro_rules! handler_error {
+    ($accounts:ty, $handler:path, $fixtures:ident $(, $arg:expr)*) => {
+        run_handler!($accounts, $handler, $fixtures $(, $arg)*).unwrap_err()
+    };
+}
+
+// Account validation error for `$fixtures` as `$accounts`, with `$ix_data` as the instruction args
+macro_rules! validation_error {
+    ($accounts:ty, $fixtures:ident, $ix_data:expr) => {{
+        let infos = crate::common::infos(&mut $fixtures);
+        let mut remaining: &[::anchor_lang::prelude::AccountInfo] = &infos;
+        let mut bumps = Default::default();
+        <$accounts as ::anchor_lang::Accounts<'_, _>>::try_accounts(
+            &viral_sync::ID, &mut remaining, $ix_data, &mut bumps, &mut ::std::collections::BTreeSet::new()
+        ).map(|_| ()).unwrap_err()
+    }};
+}
+
+pub struct Fixture {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub executable: bool,
+}
+
+impl Fixture {
+    pub fn program_account<T: AccountSerialize>(key: Pubkey, account: &T) -> Self {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        Fixture { key, owner: viral_sync::ID, lamports: 1_000_000_000, data, is_signer: false, executable: false }
+    }
+    
+    pub fn wallet(key: Pubkey, is_signer: bool) -> Self {
+        Fixture { key, owner: system_program::ID, lamports: 1_000_000_000, data: Vec::new(), is_signer, executable: false }
+    }
+    
+    // Mints are interface accounts; a minimal Token-2022 mint layout is enough
+    pub fn mint(key: Pubkey) -> Self {
+        let mut data = vec![0u8; 82];
+        data[45] = 1; // is_initialized
+        Fixture { key, owner: anchor_spl::token_2022::ID, lamports: 1_000_000_000, data, is_signer: false, executable: false }
+    }
+    
+    pub fn token_account(key: Pubkey, mint: Pubkey, owner: Pubkey) -> Self {
+        let mut data = vec![0u8; 165];
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[108] = 1; // AccountState::Initialized
+        Fixture { key, owner: anchor_spl::token_2022::ID, lamports: 1_000_000_000, data, is_signer: false, executable: false }
+    }
+    
+    pub fn token_program() -> Self {
+        Fixture { key: anchor_spl::token_2022::ID, owner: Pubkey::default(), lamports: 1, data: Vec::new(), is_signer: false, executable: true }
+    }
+    
+    pub fn system_program() -> Self {
+        Fixture { key: system_program::ID, owner: Pubkey::default(), lamports: 1, data: Vec::new(), is_signer: false, executable: true }
+    }
+    
+    // Anchor reads the program ID in an optional account's slot as None
+    pub fn none() -> Self {
+        Fixture { key: viral_sync::ID, owner: Pubkey::default(), lamports: 1, data: Vec::new(), is_signer: false, executable: true }
+    }
+}
+
+pub fn infos(fixtures: &mut [Fixture]) -> Vec<AccountInfo<'_>> {
+    fixtures
+        .iter_mut()
+        .map(|f| AccountInfo::new(&f.key, f.is_signer, true, &mut f.lamports, &mut f.data, &f.owner, f.executable, 0))
+        .collect()
+}
+
+pub struct Merchant {
+    pub merchant: Pubkey,
+    pub mint: Pubkey,
+    pub config_key: Pubkey,
+    pub config: MerchantConfig,
+    pub bond_key: Pubkey,
+    pub bond: MerchantBond,
+    pub vault_key: Pubkey,
+    pub vault: BondVault,
+    pub treasury_key: Pubkey,
+    pub treasury: TokenGeneration,
+}
+
+pub fn merchant() -> Merchant {
+    let merchant = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let (config_key, config_bump) = Pubkey::find_program_address(&[b"merchant_v4", mint.as_ref()], &viral_sync::ID);
+    let (bond_key, bond_bump) = Pubkey::find_program_address(&[b"merchant_bond", mint.as_ref()], &viral_sync::ID);
+    let (vault_key, vault_bump) = Pubkey::find_program_address(&[b"bond_vault", bond_key.as_ref()], &viral_sync::ID);
+    
+    let mut config: MerchantConfig = zeroed(MerchantConfig::LEN);
+    config.bump = config_bump;
+    config.merchant = merchant;
+    config.mint = mint;
+    config.is_active = true;
+    
+    let bond = MerchantBond {
+        bump: bond_bump,
+        merchant,
+        bonded_lamports: 0,
+        min_required_lamports: 0,
+        is_locked: true,
+        unlock_requested_at: 0,
+        vault_bump,
+        redeemed_lamports: 0,
+        redeemed_tokens: 0,
+        mint,
+    };
+    let vault = BondVault { bump: vault_bump, merchant_bond: bond_key };
+    
+    let treasury_ata = Pubkey::new_unique();
+    let (treasury_key, treasury_bump) = Pubkey::find_program_address(&[b"gen_v4", mint.as_ref(), treasury_ata.as_ref()], &viral_sync::ID);
+    let mut treasury: TokenGeneration = zeroed(TokenGeneration::LEN);
+    treasury.bump = treasury_bump;
+    treasury.mint = mint;
+    treasury.owner = treasury_ata;
+    treasury.is_treasury = true;
+    
+    Merchant { merchant, mint, config_key, config, bond_key, bond, vault_key, vault, treasury_key, treasury }
+}
+
+pub fn zeroed<T: AccountDeserialize>(len: usize) -> T {
+    T::try_deserialize_unchecked(&mut &vec![0u8; len][..]).unwrap()
+}
+
+// Accounts for a permissionless crank of a dispute against `ledger_referrer`'s ledger
+pub fn expired_dispute_fixtures(ledger_referrer: Option<Pubkey>, status: DisputeStatus, raised_at: i64) -> Vec<Fixture> {
+    let m = merchant();
+    let watchdog = Pubkey::new_unique();
+    let referral_key = Pubkey::new_unique();
+    
+    let mut referral: ReferralRecord = zeroed(ReferralRecord::LEN);
+    referral.merchant = m.merchant;
+    referral.mint = m.mint;
+    referral.referrer = Pubkey::new_unique();
+    
+    let mut ledger: CommissionLedger = zeroed(CommissionLedger::LEN);
+    ledger.referrer = ledger_referrer.unwrap_or(referral.referrer);
+    ledger.merchant = m.merchant;
+    ledger.mint = m.mint;
+    
+    let mut reputation: MerchantReputation = zeroed(MerchantReputation::LEN);
+    reputation.merchant = m.merchant;
+    
+    let (dispute_key, bump) = Pubkey::find_program_address(&[b"dispute", referral_key.as_ref(), watchdog.as_ref()], &viral_sync::ID);
+    let (escrow_key, escrow_bump) = Pubkey::find_program_address(&[b"dispute_escrow", dispute_key.as_ref()], &viral_sync::ID);
+    let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury", m.mint.as_ref()], &viral_sync::ID);
+    let dispute = DisputeRecord {
+        bump,
+        merchant: m.merchant,
+        referral: referral_key,
+        watchdog,
+        status,
+        stake_lamports: 0,
+        raised_at,
+        resolved_at: None,
+        evidence_hash: None,
+        responded_at: None,
+        disputed_amount: 0,
+        escrow_bump,
+    };
+    let escrow = DisputeEscrow { bump: escrow_bump, dispute: dispute_key };
+    
+    vec![
+        Fixture::wallet(Pubkey::new_unique(), true), // cranker
+        Fixture::program_account(dispute_key, &dispute),
+        Fixture::program_account(escrow_key, &escrow),
+        Fixture::program_account(referral_key, &referral),
+        Fixture::program_account(Pubkey::new_unique(), &ledger),
+        Fixture::program_account(Pubkey::new_unique(), &reputation),
+        Fixture::wallet(watchdog, false),
+        Fixture::wallet(m.merchant, false),
+        Fixture::mint(m.mint),
+        Fixture::wallet(treasury_authority, false),
+        Fixture::token_account(Pubkey::new_unique(), m.mint, treasury_authority),
+        Fixture::token_account(Pubkey::new_unique(), m.mint, watchdog),
+        Fixture::token_program(),
+    ]
+}
@@ -0,0 +1,83 @@
+// Commission ledger effects of raising and resolving disputes.
+
+use viral_sync::errors::ViralSyncError;
+use viral_sync::instructions::disputes::split_slashed_commission;
+use viral_sync::state::*;
+
+mod common;
+use common::zeroed;
+
+fn ledger(claimable: u64) -> CommissionLedger {
+    let mut ledger: CommissionLedger = zeroed(CommissionLedger::LEN);
+    ledger.claimable = claimable;
+    ledger
+}
+
+#[test]
+fn upheld_dispute_forfeits_the_frozen_commission() {
+    let mut ledger = ledger(1_000);
+    ledger.freeze(400).unwrap();
+    
+    assert_eq!(ledger.slash_frozen(400), 400);
+    assert_eq!(ledger.claimable, 600);
+    assert!(!ledger.frozen);
+    assert_eq!(ledger.frozen_amount, 0);
+    
+    // A second resolution has nothing left to take
+    assert_eq!(ledger.slash_frozen(400), 0);
+    assert_eq!(ledger.claimable, 600);
+}
+
+#[test]
+fn dismissed_dispute_releases_the_commission() {
+    let mut ledger = ledger(1_000);
+    ledger.freeze(400).unwrap();
+    
+    ledger.release_frozen(400);
+    assert_eq!(ledger.claimable, 1_000);
+    assert!(!ledger.frozen);
+    assert_eq!(ledger.frozen_amount, 0);
+}
+
+#[test]
+fn disputes_cannot_freeze_more_than_is_claimable() {
+    let mut ledger = ledger(1_000);
+    assert_eq!(ledger.freeze(0).unwrap_err(), ViralSyncError::InvalidDisputeAmount.into());
+    assert_eq!(ledger.freeze(1_001).unwrap_err(), ViralSyncError::InvalidDisputeAmount.into());
+    
+    ledger.freeze(700).unwrap();
+    assert_eq!(ledger.freeze(301).unwrap_err(), ViralSyncError::InvalidDisputeAmount.into());
+    ledger.freeze(300).unwrap();
+    assert_eq!(ledger.frozen_amount, 1_000);
+}
+
+#[test]
+fn concurrent_disputes_stack_and_resolve_independently() {
+    let mut ledger = ledger(1_000);
+    ledger.freeze(300).unwrap();
+    ledger.freeze(200).unwrap();
+    assert_eq!(ledger.frozen_amount, 500);
+    
+    // Dismissing the first leaves the second frozen
+    ledger.release_frozen(300);
+    assert!(ledger.frozen);
+    assert_eq!(ledger.frozen_amount, 200);
+    assert_eq!(ledger.claimable, 1_000);
+    
+    assert_eq!(ledger.slash_frozen(200), 200);
+    assert!(!ledger.frozen);
+    assert_eq!(ledger.claimable, 800);
+}
+
+#[test]
+fn forfeited_commission_is_split_without_loss() {
+    assert_eq!(split_slashed_commission(1_000), (500, 250, 250));
+    assert_eq!(split_slashed_commission(0), (0, 0, 0));
+    
+    // Rounding dust stays in the treasury
+    for amount in [1, 3, 7, 999, u64::MAX] {
+        let (reward, retained, burned) = split_slashed_commission(amount);
+        assert_eq!(reward as u128 + retained as u128 + burned as u128, amount as u128);
+        assert!(reward >= burned);
+    }
+}
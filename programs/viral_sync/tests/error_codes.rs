@@ -0,0 +1,459 @@
+// Each failure path returns its own error code, so relayers can tell them apart. Handlers run
+// off-chain against fixture accounts with a stubbed clock.
+
+use anchor_lang::prelude::*;
+use viral_sync::errors::ViralSyncError;
+use viral_sync::instructions::*;
+use viral_sync::state::*;
+
+#[macro_use]
+mod common;
+use common::*;
+
+fn geo_fence(is_active: bool, allow_non_geo_redemption: bool) -> Vec<Fixture> {
+    let mut fence: GeoFence = zeroed(GeoFence::LEN);
+    fence.is_active = is_active;
+    fence.allow_non_geo_redemption = allow_non_geo_redemption;
+    vec![
+        Fixture::program_account(Pubkey::new_unique(), &fence),
+        Fixture::wallet(Pubkey::new_unique(), true),
+    ]
+}
+
+// Redeemer, referrer and ledger for one referral on `m`'s mint, with the redemption pending on slot 0
+struct RedemptionSlot {
+    redeemer_key: Pubkey,
+    redeemer: TokenGeneration,
+    referral_key: Pubkey,
+    referral: ReferralRecord,
+    referrer_key: Pubkey,
+    referrer: TokenGeneration,
+    ledger: CommissionLedger,
+}
+
+impl RedemptionSlot {
+    fn new(m: &Merchant) -> Self {
+        let referral_key = Pubkey::new_unique();
+        let mut referral: ReferralRecord = zeroed(ReferralRecord::LEN);
+        referral.merchant = m.merchant;
+        referral.mint = m.mint;
+        referral.referrer = Pubkey::new_unique();
+        referral.committed_commission_bps = 1_000;
+        referral.is_active = true;
+        
+        let mut redeemer: TokenGeneration = zeroed(TokenGeneration::LEN);
+        redeemer.mint = m.mint;
+        redeemer.redemption_pending = true;
+        redeemer.active_referrer_slots = 1;
+        redeemer.referrer_slots[0].referrer = referral.referrer;
+        redeemer.referrer_slots[0].referral_record = referral_key;
+        redeemer.redemption_slot_consumed[0] = 100;
+        
+        let mut referrer: TokenGeneration = zeroed(TokenGeneration::LEN);
+        referrer.mint = m.mint;
+        referrer.owner = referral.referrer;
+        
+        let mut ledger: CommissionLedger = zeroed(CommissionLedger::LEN);
+        ledger.referrer = referral.referrer;
+        ledger.merchant = m.merchant;
+        ledger.mint = m.mint;
+        
+        RedemptionSlot {
+            redeemer_key: Pubkey::new_unique(),
+            redeemer,
+            referral_key,
+            referral,
+            referrer_key: Pubkey::new_unique(),
+            referrer,
+            ledger,
+        }
+    }
+    
+    fn fixtures(&self, config_key: Pubkey, config: &MerchantConfig) -> Vec<Fixture> {
+        vec![
+            Fixture::wallet(Pubkey::new_unique(), true),
+            Fixture::program_account(self.redeemer_key, &self.redeemer),
+            Fixture::program_account(self.referral_key, &self.referral),
+            Fixture::program_account(self.referrer_key, &self.referrer),
+            Fixture::program_account(Pubkey::new_unique(), &self.ledger),
+            Fixture::program_account(config_key, config),
+        ]
+    }
+}
+
+fn referral(expires_at: i64, commission_earned: u64) -> Vec<Fixture> {
+    let mut referral: ReferralRecord = zeroed(ReferralRecord::LEN);
+    referral.expires_at = expires_at;
+    referral.commission_earned = commission_earned;
+    vec![
+        Fixture::program_account(Pubkey::new_unique(), &referral),
+        Fixture::wallet(Pubkey::new_unique(), true),
+    ]
+}
+
+#[test]
+fn initiating_a_second_close_is_merchant_inactive() {
+    let mut m = merchant();
+    m.config.is_active = false;
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::wallet(m.merchant, true),
+    ];
+    let err = handler_error!(InitiateCloseMerchant, initiate_close_merchant, fixtures);
+    assert_eq!(err, ViralSyncError::MerchantInactive.into());
+}
+
+#[test]
+fn finalizing_inside_the_window_with_supply_is_close_window_not_expired() {
+    let mut m = merchant();
+    m.config.is_active = false;
+    m.config.close_initiated_at = NOW - 60;
+    m.config.close_window_ends_at = NOW + 60;
+    let mut mint = Fixture::mint(m.mint);
+    mint.data[36..44].copy_from_slice(&1u64.to_le_bytes()); // supply
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::wallet(m.merchant, true),
+        Fixture::program_account(m.vault_key, &m.vault),
+        mint,
+        Fixture::program_account(m.treasury_key, &m.treasury),
+    ];
+    let err = handler_error!(FinalizeCloseMerchant, finalize_close_merchant, fixtures);
+    assert_eq!(err, ViralSyncError::CloseWindowNotExpired.into());
+}
+
+#[test]
+fn cancelling_after_share_redemptions_is_bond_shares_redeemed() {
+    let mut m = merchant();
+    m.config.is_active = false;
+    m.config.close_initiated_at = NOW - 60;
+    m.config.close_window_ends_at = NOW + 60;
+    m.bond.record_share_redemption(0, 1).unwrap();
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::wallet(m.merchant, true),
+    ];
+    let err = handler_error!(CancelCloseMerchant, cancel_close_merchant, fixtures);
+    assert_eq!(err, ViralSyncError::BondSharesRedeemed.into());
+    
+    // Without a bond there is nothing to protect
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::none(),
+        Fixture::wallet(m.merchant, true),
+    ];
+    run_handler!(CancelCloseMerchant, cancel_close_merchant, fixtures).unwrap();
+}
+
+#[test]
+fn withdrawing_a_locked_bond_is_bond_locked() {
+    let m = merchant();
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::program_account(m.vault_key, &m.vault),
+        Fixture::wallet(m.merchant, true),
+    ];
+    let err = handler_error!(WithdrawBond, withdraw_bond, fixtures, 1);
+    assert_eq!(err, ViralSyncError::BondLocked.into());
+}
+
+#[test]
+fn bond_stays_put_while_a_close_is_pending() {
+    let mut m = merchant();
+    m.config.is_active = false;
+    m.config.close_initiated_at = NOW - 60;
+    m.bond.request_unlock(NOW - BOND_UNLOCK_DELAY_SECS);
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::wallet(m.merchant, true),
+    ];
+    let err = handler_error!(RequestBondUnlock, request_bond_unlock, fixtures);
+    assert_eq!(err, ViralSyncError::MerchantSunset.into());
+    
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::program_account(m.vault_key, &m.vault),
+        Fixture::wallet(m.merchant, true),
+    ];
+    let err = handler_error!(WithdrawBond, withdraw_bond, fixtures, 1);
+    assert_eq!(err, ViralSyncError::MerchantSunset.into());
+}
+
+#[test]
+fn geo_redemption_errors_are_distinct() {
+    let mut fixtures = geo_fence(false, true);
+    let err = handler_error!(RedeemWithGeo, redeem_with_geo, fixtures, 0, 0, Vec::new());
+    assert_eq!(err, ViralSyncError::GeoFenceInactive.into());
+    
+    // No attestation and no non-geo fallback
+    let mut fixtures = geo_fence(true, false);
+    let err = handler_error!(RedeemWithGeo, redeem_with_geo, fixtures, 0, 0, Vec::new());
+    assert_eq!(err, ViralSyncError::InvalidAttestation.into());
+}
+
+#[test]
+fn referral_cleanup_errors_are_distinct() {
+    let mut fixtures = referral(NOW + 60, 0);
+    let err = handler_error!(CloseExpiredReferral, close_expired_referral, fixtures);
+    assert_eq!(err, ViralSyncError::ReferralNotExpired.into());
+    
+    let mut fixtures = referral(NOW - 60, 100);
+    let err = handler_error!(CloseExpiredReferral, close_expired_referral, fixtures);
+    assert_eq!(err, ViralSyncError::OutstandingCommission.into());
+}
+
+#[test]
+fn expired_dispute_errors_are_distinct() {
+    let mut fixtures = expired_dispute_fixtures(None, DisputeStatus::Pending, NOW);
+    let err = handler_error!(ResolveExpiredDispute, resolve_expired_dispute, fixtures);
+    assert_eq!(err, ViralSyncError::DisputeWindowOpen.into());
+    
+    let mut fixtures = expired_dispute_fixtures(None, DisputeStatus::InArbitration, 0);
+    let err = handler_error!(ResolveExpiredDispute, resolve_expired_dispute, fixtures);
+    assert_eq!(err, ViralSyncError::DisputeNotPending.into());
+}
+
+#[test]
+fn arbiter_rulings_refund_a_summoned_vote_only_to_its_payer() {
+    let arbiter = Pubkey::new_unique();
+    let (arbitration_key, bump) = Pubkey::find_program_address(&[b"arbitration"], &viral_sync::ID);
+    let mut arbitration: Arbitration = zeroed(Arbitration::LEN);
+    arbitration.bump = bump;
+    arbitration.arbiter = arbiter;
+    
+    let mut settlement = expired_dispute_fixtures(None, DisputeStatus::InArbitration, 0);
+    settlement.remove(0); // cranker
+    let dispute_key = settlement[0].key;
+    let (vote_key, vote_bump) = Pubkey::find_program_address(&[b"dispute_vote", dispute_key.as_ref()], &viral_sync::ID);
+    let mut vote: DisputeVote = zeroed(DisputeVote::LEN);
+    vote.bump = vote_bump;
+    vote.dispute = dispute_key;
+    vote.payer = Pubkey::new_unique();
+    
+    let mut fixtures = vec![
+        Fixture::program_account(arbitration_key, &arbitration),
+        Fixture::wallet(arbiter, true),
+    ];
+    fixtures.extend(settlement);
+    fixtures.push(Fixture::program_account(vote_key, &vote));
+    fixtures.push(Fixture::wallet(Pubkey::new_unique(), false));
+    let err = handler_error!(ResolveDispute, resolve_dispute, fixtures, true);
+    assert_eq!(err, ViralSyncError::AccessDenied.into());
+}
+
+#[test]
+fn extending_a_session_into_the_past_is_invalid_session_expiry() {
+    let authority = Pubkey::new_unique();
+    let generation_key = Pubkey::new_unique();
+    let generation: TokenGeneration = zeroed(TokenGeneration::LEN);
+    let mut session: SessionKey = zeroed(SessionKey::LEN);
+    session.authority = authority;
+    session.target_generation = generation_key;
+    session.is_active = true;
+    session.expires_at = NOW + 60;
+    let mut fixtures = vec![
+        Fixture::program_account(Pubkey::new_unique(), &session),
+        Fixture::program_account(generation_key, &generation),
+        Fixture::wallet(authority, true),
+    ];
+    let err = handler_error!(ExtendSessionKey, extend_session_key, fixtures, NOW - 1);
+    assert_eq!(err, ViralSyncError::InvalidSessionExpiry.into());
+}
+
+#[test]
+fn another_mints_merchant_config_is_merchant_mint_mismatch() {
+    let m = merchant();
+    let slot = RedemptionSlot::new(&m);
+    let other = merchant();
+    let mut fixtures = slot.fixtures(other.config_key, &other.config);
+    let err = validation_error!(ProcessRedemptionSlot, fixtures, &[0]);
+    assert_eq!(err, ViralSyncError::MerchantMintMismatch.into());
+}
+
+#[test]
+fn redemption_slots_only_accept_their_own_referral() {
+    let m = merchant();
+    let mut slot = RedemptionSlot::new(&m);
+    slot.referral_key = Pubkey::new_unique();
+    let mut fixtures = slot.fixtures(m.config_key, &m.config);
+    let err = validation_error!(ProcessRedemptionSlot, fixtures, &[0]);
+    assert_eq!(err, ViralSyncError::ReferralSlotMismatch.into());
+    
+    // Slot 1 is attributed elsewhere
+    let slot = RedemptionSlot::new(&m);
+    let mut fixtures = slot.fixtures(m.config_key, &m.config);
+    let err = validation_error!(ProcessRedemptionSlot, fixtures, &[1]);
+    assert_eq!(err, ViralSyncError::ReferralSlotMismatch.into());
+    
+    let mut slot = RedemptionSlot::new(&m);
+    slot.ledger.referrer = Pubkey::new_unique();
+    let mut fixtures = slot.fixtures(m.config_key, &m.config);
+    let err = validation_error!(ProcessRedemptionSlot, fixtures, &[0]);
+    assert_eq!(err, ViralSyncError::AccessDenied.into());
+}
+
+#[test]
+fn ineligible_referrers_still_build_momentum_without_commission() {
+    let mut m = merchant();
+    m.config.min_poi_score_for_commission = 1;
+    m.config.max_tokens_per_referral = 100;
+    let slot = RedemptionSlot::new(&m);
+    let mut fixtures = slot.fixtures(m.config_key, &m.config);
+    run_handler!(@ix &[0], ProcessRedemptionSlot, process_redemption_slot, fixtures, 0).unwrap();
+    
+    let ledger: CommissionLedger = CommissionLedger::try_deserialize(&mut &fixtures[4].data[..]).unwrap();
+    let referrer: TokenGeneration = TokenGeneration::try_deserialize(&mut &fixtures[3].data[..]).unwrap();
+    assert_eq!(ledger.claimable, 0);
+    assert_eq!(referrer.poi_momentum, POI_POINTS_PER_REFERRAL);
+}
+
+// Payout accounts for `slot`'s ledger, paying into an account owned by `referrer_ata_owner`
+fn payout_fixtures(m: &Merchant, slot: &RedemptionSlot, referrer_ata_owner: Pubkey) -> Vec<Fixture> {
+    let (treasury_signer, _) = Pubkey::find_program_address(&[b"treasury", m.mint.as_ref()], &viral_sync::ID);
+    vec![
+        Fixture::program_account(Pubkey::new_unique(), &slot.ledger),
+        Fixture::program_account(slot.referrer_key, &slot.referrer),
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.treasury_key, &m.treasury),
+        Fixture::token_account(m.treasury.owner, m.mint, treasury_signer),
+        Fixture::token_account(Pubkey::new_unique(), m.mint, referrer_ata_owner),
+        Fixture::mint(m.mint),
+        Fixture::wallet(treasury_signer, false),
+        Fixture::token_program(),
+    ]
+}
+
+#[test]
+fn commission_claims_credit_only_the_ledgers_referrer() {
+    let m = merchant();
+    let mut slot = RedemptionSlot::new(&m);
+    slot.referrer.owner = Pubkey::new_unique();
+    let mut fixtures = vec![Fixture::wallet(slot.referral.referrer, true)];
+    fixtures.extend(payout_fixtures(&m, &slot, slot.referral.referrer));
+    let err = validation_error!(ClaimCommission, fixtures, &[]);
+    assert_eq!(err, ViralSyncError::ReferrerGenerationMismatch.into());
+}
+
+#[test]
+fn commission_claims_need_the_referrers_signature_and_account() {
+    let m = merchant();
+    let mut slot = RedemptionSlot::new(&m);
+    slot.ledger.claimable = 50;
+    let referrer = slot.referral.referrer;
+    let claim_fixtures = |signer: Pubkey, is_signer: bool, referrer_ata_owner: Pubkey| {
+        let mut fixtures = vec![Fixture::wallet(signer, is_signer)];
+        fixtures.extend(payout_fixtures(&m, &slot, referrer_ata_owner));
+        fixtures
+    };
+    
+    let mut fixtures = claim_fixtures(referrer, false, referrer);
+    let err = validation_error!(ClaimCommission, fixtures, &[]);
+    assert_eq!(err, anchor_lang::error::ErrorCode::AccountNotSigner.into());
+    
+    let mut fixtures = claim_fixtures(Pubkey::new_unique(), true, referrer);
+    let err = validation_error!(ClaimCommission, fixtures, &[]);
+    assert_eq!(err, ViralSyncError::AccessDenied.into());
+    
+    let mut fixtures = claim_fixtures(referrer, true, Pubkey::new_unique());
+    let err = validation_error!(ClaimCommission, fixtures, &[]);
+    assert_eq!(err, ViralSyncError::AccessDenied.into());
+    
+    let mut fixtures = claim_fixtures(referrer, true, referrer);
+    run_handler!(ClaimCommission, claim_commission, fixtures).unwrap();
+    let ledger = CommissionLedger::try_deserialize(&mut &fixtures[1].data[..]).unwrap();
+    let generation = TokenGeneration::try_deserialize(&mut &fixtures[2].data[..]).unwrap();
+    assert_eq!((ledger.claimable, ledger.total_claimed), (0, 50));
+    assert_eq!((generation.total_commission_claimed, generation.claim_count), (50, 1));
+}
+
+#[test]
+fn finalizing_another_owners_buffer_is_access_denied() {
+    let mut generation: TokenGeneration = zeroed(TokenGeneration::LEN);
+    generation.owner = Pubkey::new_unique();
+    let mut fixtures = vec![
+        Fixture::program_account(Pubkey::new_unique(), &generation),
+        Fixture::wallet(Pubkey::new_unique(), true),
+    ];
+    let err = validation_error!(FinalizeInbound, fixtures, &[]);
+    assert_eq!(err, ViralSyncError::AccessDenied.into());
+}
+
+// Session PDA for `delegate` on `generation_key`, valid at NOW
+fn session_for(generation_key: Pubkey, delegate: Pubkey, permissions: u8) -> (Pubkey, SessionKey) {
+    let (key, bump) = Pubkey::find_program_address(
+        &[b"session", generation_key.as_ref(), delegate.as_ref(), &0u32.to_le_bytes()],
+        &viral_sync::ID,
+    );
+    let mut session: SessionKey = zeroed(SessionKey::LEN);
+    session.bump = bump;
+    session.target_generation = generation_key;
+    session.delegate = delegate;
+    session.is_active = true;
+    session.expires_at = NOW + 60;
+    session.permissions = permissions;
+    (key, session)
+}
+
+#[test]
+fn session_finalize_needs_the_finalize_bit() {
+    let generation_key = Pubkey::new_unique();
+    let delegate = Pubkey::new_unique();
+    let mut generation: TokenGeneration = zeroed(TokenGeneration::LEN);
+    generation.buffer_pending = 1;
+    
+    let (session_key, session) = session_for(generation_key, delegate, SESSION_PERMISSION_ALL & !SESSION_PERMISSION_FINALIZE);
+    let mut fixtures = vec![
+        Fixture::program_account(session_key, &session),
+        Fixture::wallet(delegate, true),
+        Fixture::program_account(generation_key, &generation),
+    ];
+    let err = handler_error!(FinalizeInboundWithSession, finalize_inbound_with_session, fixtures);
+    assert_eq!(err, ViralSyncError::SessionPermissionDenied.into());
+    
+    let (session_key, session) = session_for(generation_key, delegate, SESSION_PERMISSION_FINALIZE);
+    let mut fixtures = vec![
+        Fixture::program_account(session_key, &session),
+        Fixture::wallet(delegate, true),
+        Fixture::program_account(generation_key, &generation),
+    ];
+    run_handler!(FinalizeInboundWithSession, finalize_inbound_with_session, fixtures).unwrap();
+    let generation = TokenGeneration::try_deserialize(&mut &fixtures[2].data[..]).unwrap();
+    assert_eq!(generation.buffer_pending, 0);
+}
+
+#[test]
+fn session_claims_need_the_claim_bit_and_pay_the_owner() {
+    let m = merchant();
+    let mut slot = RedemptionSlot::new(&m);
+    slot.ledger.claimable = 50;
+    let delegate = Pubkey::new_unique();
+    let claim_fixtures = |session_key: Pubkey, session: &SessionKey, referrer_ata_owner: Pubkey| {
+        let mut fixtures = vec![
+            Fixture::program_account(session_key, session),
+            Fixture::wallet(delegate, true),
+        ];
+        fixtures.extend(payout_fixtures(&m, &slot, referrer_ata_owner));
+        fixtures
+    };
+    
+    let (session_key, session) = session_for(slot.referrer_key, delegate, SESSION_PERMISSION_CLAIM_COMMISSION);
+    let mut fixtures = claim_fixtures(session_key, &session, Pubkey::new_unique());
+    let err = validation_error!(ClaimCommissionWithSession, fixtures, &[]);
+    assert_eq!(err, ViralSyncError::AccessDenied.into());
+    
+    let (session_key, session) = session_for(slot.referrer_key, delegate, SESSION_PERMISSION_ALL & !SESSION_PERMISSION_CLAIM_COMMISSION);
+    let mut fixtures = claim_fixtures(session_key, &session, slot.referral.referrer);
+    let err = handler_error!(ClaimCommissionWithSession, claim_commission_with_session, fixtures);
+    assert_eq!(err, ViralSyncError::SessionPermissionDenied.into());
+    
+    let (session_key, session) = session_for(slot.referrer_key, delegate, SESSION_PERMISSION_CLAIM_COMMISSION);
+    let mut fixtures = claim_fixtures(session_key, &session, slot.referral.referrer);
+    run_handler!(ClaimCommissionWithSession, claim_commission_with_session, fixtures).unwrap();
+    let ledger = CommissionLedger::try_deserialize(&mut &fixtures[2].data[..]).unwrap();
+    assert_eq!(ledger.claimable, 0);
+}
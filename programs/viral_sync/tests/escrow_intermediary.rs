@@ -0,0 +1,92 @@
+// The hook only honours `is_intermediary` on generations owned by the escrow PDA they describe,
+// and an unclaimed escrow only gives its creator back the buckets the deposit came from.
+
+use anchor_lang::prelude::*;
+use viral_sync::state::*;
+
+mod common;
+use common::zeroed;
+
+fn escrow_generation(mint: Pubkey, creator: Pubkey, nonce: u64) -> TokenGeneration {
+    let (escrow, bump) = Pubkey::find_program_address(
+        &[b"escrow", mint.as_ref(), creator.as_ref(), &nonce.to_le_bytes()],
+        &viral_sync::ID,
+    );
+    let mut gen: TokenGeneration = zeroed(TokenGeneration::LEN);
+    gen.mint = mint;
+    gen.owner = escrow;
+    gen.is_intermediary = true;
+    gen.original_sender = creator;
+    gen.escrow_creator = creator;
+    gen.escrow_nonce = nonce;
+    gen.escrow_bump = bump;
+    gen
+}
+
+#[test]
+fn escrow_owned_generation_is_a_valid_intermediary() {
+    let gen = escrow_generation(Pubkey::new_unique(), Pubkey::new_unique(), 7);
+    assert!(gen.is_valid_intermediary());
+}
+
+#[test]
+fn user_generation_cannot_pose_as_intermediary() {
+    let creator = Pubkey::new_unique();
+    let mut gen = escrow_generation(Pubkey::new_unique(), creator, 7);
+    
+    // Same escrow seeds, but the generation belongs to an ordinary wallet
+    gen.owner = Pubkey::new_unique();
+    assert!(!gen.is_valid_intermediary());
+    
+    // A wallet that copies another escrow's seeds onto its own generation is still rejected
+    let mut forged: TokenGeneration = zeroed(TokenGeneration::LEN);
+    forged.mint = gen.mint;
+    forged.owner = creator;
+    forged.is_intermediary = true;
+    forged.escrow_creator = gen.escrow_creator;
+    forged.escrow_nonce = gen.escrow_nonce;
+    forged.escrow_bump = gen.escrow_bump;
+    assert!(!forged.is_valid_intermediary());
+}
+
+#[test]
+fn seeds_must_match_the_owning_escrow() {
+    let mut gen = escrow_generation(Pubkey::new_unique(), Pubkey::new_unique(), 7);
+    gen.escrow_nonce = 8;
+    assert!(!gen.is_valid_intermediary());
+}
+
+#[test]
+fn unflagged_generation_is_never_an_intermediary() {
+    let mut gen = escrow_generation(Pubkey::new_unique(), Pubkey::new_unique(), 7);
+    gen.is_intermediary = false;
+    assert!(!gen.is_valid_intermediary());
+}
+
+fn deposit(gen1: u64, gen2: u64, dead: u64) -> Escrow {
+    let mut escrow: Escrow = zeroed(Escrow::LEN);
+    escrow.deposited_gen1 = gen1;
+    escrow.deposited_gen2 = gen2;
+    escrow.deposited_dead = dead;
+    escrow.amount = gen1 + gen2 + dead;
+    escrow
+}
+
+#[test]
+fn harvest_never_turns_dead_tokens_into_gen1() {
+    // 1 gen1 shared alongside 1_000 dead tokens bought on a DEX, never claimed
+    let escrow = deposit(1, 0, 1_000);
+    assert_eq!(escrow.unclaimed_split(1_001), (1, 0, 1_000));
+}
+
+#[test]
+fn claims_and_fees_are_charged_to_gen1_first() {
+    let escrow = deposit(300, 200, 500);
+    assert_eq!(escrow.unclaimed_split(1_000), (300, 200, 500));
+    assert_eq!(escrow.unclaimed_split(800), (100, 200, 500));
+    assert_eq!(escrow.unclaimed_split(600), (0, 100, 500));
+    assert_eq!(escrow.unclaimed_split(0), (0, 0, 0));
+    
+    // Tokens sent straight to the escrow account come back without attribution
+    assert_eq!(escrow.unclaimed_split(1_050), (300, 200, 550));
+}
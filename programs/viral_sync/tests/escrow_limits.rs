@@ -0,0 +1,152 @@
+// An escrow deposit is one transfer but can fan out into many claims; each claim must pay the
+// same per-referral limits a direct peer share would. Claims run through claim_escrow off-chain.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use viral_sync::errors::ViralSyncError;
+use viral_sync::instructions::*;
+use viral_sync::state::*;
+
+#[macro_use]
+mod common;
+use common::*;
+
+const SLOTS_PER_DAY: u64 = 216_000;
+const TODAY: u64 = NOW_SLOT / SLOTS_PER_DAY;
+const CLAIM_CODE: &[u8] = b"spring-launch";
+
+// A creator's open escrow on a merchant with 10..=100 token referrals
+struct OpenEscrow {
+    m: Merchant,
+    escrow_key: Pubkey,
+    escrow: Escrow,
+    escrow_generation_key: Pubkey,
+    escrow_generation: TokenGeneration,
+    creator_generation_key: Pubkey,
+    creator_generation: TokenGeneration,
+}
+
+impl OpenEscrow {
+    // `max_claimers` 0 is a single escrow claimable in partial amounts
+    fn new(max_per_day: u16, max_claimers: u16, amount_per_claim: u64) -> Self {
+        let mut m = merchant();
+        m.config.min_tokens_per_referral = 10;
+        m.config.max_tokens_per_referral = 100;
+        m.config.max_referrals_per_wallet_per_day = max_per_day;
+        m.config.slots_per_day = SLOTS_PER_DAY;
+        
+        let creator = Pubkey::new_unique();
+        let (escrow_key, escrow_bump) = Pubkey::find_program_address(
+            &[b"escrow", m.mint.as_ref(), creator.as_ref(), &0u64.to_le_bytes()],
+            &viral_sync::ID,
+        );
+        let mut escrow: Escrow = zeroed(Escrow::space_for(max_claimers));
+        escrow.bump = escrow_bump;
+        escrow.mint = m.mint;
+        escrow.creator = creator;
+        escrow.amount = 1_000;
+        escrow.expires_at = NOW + 86_400;
+        escrow.claim_code_hash = Some(hash(CLAIM_CODE).to_bytes());
+        escrow.max_claimers = max_claimers;
+        escrow.amount_per_claim = amount_per_claim;
+        
+        let (escrow_generation_key, escrow_generation) = generation(&m, escrow_key);
+        let (creator_generation_key, creator_generation) = generation(&m, creator);
+        OpenEscrow {
+            m,
+            escrow_key,
+            escrow,
+            escrow_generation_key,
+            escrow_generation,
+            creator_generation_key,
+            creator_generation,
+        }
+    }
+    
+    // One claim by a fresh wallet presenting the claim code
+    fn claim(&mut self, amount: u64) -> Result<()> {
+        let claimant = Pubkey::new_unique();
+        let (_, dest_generation) = generation(&self.m, claimant);
+        let mut escrow = Fixture::program_account(self.escrow_key, &self.escrow);
+        escrow.data.resize(Escrow::space_for(self.escrow.max_claimers), 0);
+        let mut fixtures = vec![
+            escrow,
+            Fixture::program_account(self.escrow_generation_key, &self.escrow_generation),
+            Fixture::program_account(Pubkey::new_unique(), &dest_generation),
+            Fixture::program_account(self.creator_generation_key, &self.creator_generation),
+            Fixture::program_account(self.m.config_key, &self.m.config),
+            Fixture::token_account(Pubkey::new_unique(), self.m.mint, self.escrow_key),
+            Fixture::token_account(Pubkey::new_unique(), self.m.mint, claimant),
+            Fixture::wallet(claimant, true),
+            Fixture::mint(self.m.mint),
+            Fixture::token_program(),
+        ];
+        run_handler!(ClaimEscrow, claim_escrow, fixtures, amount, Some(CLAIM_CODE.to_vec()))?;
+        
+        self.escrow = Escrow::try_deserialize(&mut &fixtures[0].data[..])?;
+        self.creator_generation = TokenGeneration::try_deserialize(&mut &fixtures[3].data[..])?;
+        Ok(())
+    }
+}
+
+fn generation(m: &Merchant, owner: Pubkey) -> (Pubkey, TokenGeneration) {
+    let (key, bump) = Pubkey::find_program_address(&[b"gen_v4", m.mint.as_ref(), owner.as_ref()], &viral_sync::ID);
+    let mut generation: TokenGeneration = zeroed(TokenGeneration::LEN);
+    generation.bump = bump;
+    generation.mint = m.mint;
+    generation.owner = owner;
+    (key, generation)
+}
+
+fn assert_error(result: Result<()>, expected: ViralSyncError) {
+    assert_eq!(result.unwrap_err(), expected.into());
+}
+
+#[test]
+fn link_drop_claims_count_against_creator_daily_limit() {
+    let mut escrow = OpenEscrow::new(3, 10, 50);
+    
+    // A 10-claim link drop funded in one deposit still yields only three referrals today
+    for _ in 0..3 {
+        escrow.claim(50).unwrap();
+    }
+    assert_error(escrow.claim(50), ViralSyncError::DailyShareLimitExceeded);
+    assert_eq!(escrow.escrow.claimers.len(), 3);
+    assert_eq!(escrow.creator_generation.shares_today, 3);
+}
+
+#[test]
+fn yesterdays_shares_do_not_limit_todays_claims() {
+    let mut escrow = OpenEscrow::new(3, 10, 50);
+    escrow.creator_generation.share_limit_day = TODAY - 1;
+    escrow.creator_generation.shares_today = 3;
+    
+    escrow.claim(50).unwrap();
+    assert_eq!(escrow.creator_generation.share_limit_day, TODAY);
+    assert_eq!(escrow.creator_generation.shares_today, 1);
+}
+
+#[test]
+fn escrow_and_peer_shares_share_one_daily_budget() {
+    let mut escrow = OpenEscrow::new(2, 0, 0);
+    
+    // One direct peer share already metered by the transfer hook today
+    escrow.creator_generation.share_limit_day = TODAY;
+    escrow.creator_generation.shares_today = 1;
+    
+    escrow.claim(20).unwrap();
+    assert_error(escrow.claim(20), ViralSyncError::DailyShareLimitExceeded);
+    assert_eq!(escrow.escrow.claimed, 20);
+}
+
+#[test]
+fn escrow_claims_respect_per_referral_bounds() {
+    let mut escrow = OpenEscrow::new(u16::MAX, 0, 0);
+    
+    assert_error(escrow.claim(9), ViralSyncError::BelowMinimum);
+    assert_error(escrow.claim(101), ViralSyncError::ExceedsMaximum);
+    
+    // Rejected claims do not consume the daily budget
+    assert_eq!(escrow.creator_generation.shares_today, 0);
+    assert_eq!(escrow.escrow.claimed, 0);
+}
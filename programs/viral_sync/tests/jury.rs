@@ -0,0 +1,257 @@
+// Juror pool membership, jury selection and the commit-reveal state machine.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use viral_sync::errors::ViralSyncError;
+use viral_sync::instructions::*;
+use viral_sync::state::*;
+
+#[macro_use]
+mod common;
+use common::{zeroed, Fixture, NOW, NOW_SLOT};
+
+fn arbitration_with(pool_size: usize) -> (Arbitration, Vec<Pubkey>) {
+    let mut arbitration: Arbitration = zeroed(Arbitration::LEN);
+    let jurors: Vec<Pubkey> = (0..pool_size).map(|_| Pubkey::new_unique()).collect();
+    for juror in &jurors {
+        arbitration.add_juror(*juror).unwrap();
+    }
+    (arbitration, jurors)
+}
+
+fn seated_vote() -> DisputeVote {
+    DisputeVote {
+        bump: 255,
+        dispute: Pubkey::new_unique(),
+        payer: Pubkey::new_unique(),
+        jurors: [(); JURORS_PER_DISPUTE].map(|_| Pubkey::new_unique()),
+        commitments: [None; JURORS_PER_DISPUTE],
+        votes: [None; JURORS_PER_DISPUTE],
+        commit_deadline: NOW + JUROR_COMMIT_SECS,
+        reveal_deadline: NOW + JUROR_COMMIT_SECS + JUROR_REVEAL_SECS,
+        draw_slot: 1,
+    }
+}
+
+fn cast(vote: &mut DisputeVote, seat: usize, uphold: bool) {
+    let juror = vote.jurors[seat];
+    let salt = [seat as u8; 32];
+    vote.commit(&juror, vote_commitment(uphold, &salt, &juror), NOW).unwrap();
+    vote.reveal(&juror, uphold, &salt, vote.commit_deadline + 1).unwrap();
+}
+
+#[test]
+fn pool_rejects_duplicates_and_overflow() {
+    let (mut arbitration, jurors) = arbitration_with(MAX_JUROR_POOL);
+    assert_eq!(arbitration.add_juror(jurors[0]).unwrap_err(), ViralSyncError::JurorAlreadyRegistered.into());
+    assert_eq!(arbitration.add_juror(Pubkey::new_unique()).unwrap_err(), ViralSyncError::JurorPoolFull.into());
+    
+    arbitration.remove_juror(&jurors[0]);
+    assert_eq!(arbitration.pool().len(), MAX_JUROR_POOL - 1);
+    assert!(!arbitration.pool().contains(&jurors[0]));
+    assert!(arbitration.pool().contains(&jurors[MAX_JUROR_POOL - 1]));
+    arbitration.add_juror(jurors[0]).unwrap();
+}
+
+#[test]
+fn a_full_pool_admits_only_a_higher_stake() {
+    let (mut arbitration, jurors) = arbitration_with(MAX_JUROR_POOL);
+    let mut evicted: JurorStake = zeroed(JurorStake::LEN);
+    evicted.juror = jurors[3];
+    evicted.stake_lamports = MIN_JUROR_STAKE_LAMPORTS;
+    let newcomer = Pubkey::new_unique();
+    
+    assert_eq!(
+        arbitration.replace_juror(&mut evicted, newcomer, MIN_JUROR_STAKE_LAMPORTS, NOW).unwrap_err(),
+        ViralSyncError::JurorStakeTooLow.into()
+    );
+    
+    arbitration.replace_juror(&mut evicted, newcomer, MIN_JUROR_STAKE_LAMPORTS + 1, NOW).unwrap();
+    assert_eq!(arbitration.pool().len(), MAX_JUROR_POOL);
+    assert!(arbitration.pool().contains(&newcomer));
+    assert!(!arbitration.pool().contains(&jurors[3]));
+    assert_eq!(evicted.exit_requested_at, NOW);
+    
+    // Someone already out of the pool cannot be evicted again
+    assert_eq!(
+        arbitration.replace_juror(&mut evicted, Pubkey::new_unique(), u64::MAX, NOW).unwrap_err(),
+        ViralSyncError::JurorNotInPool.into()
+    );
+}
+
+#[test]
+fn selection_is_deterministic_distinct_and_skips_parties() {
+    let (arbitration, jurors) = arbitration_with(8);
+    let parties = [jurors[0], jurors[1]];
+    
+    let seed = [7u8; 32];
+    let jury = arbitration.select_jurors(&seed, &parties).unwrap();
+    assert_eq!(jury, arbitration.select_jurors(&seed, &parties).unwrap());
+    for (i, juror) in jury.iter().enumerate() {
+        assert!(!parties.contains(juror));
+        assert!(!jury[i + 1..].contains(juror));
+    }
+    
+    // A different slot hash draws a different jury often enough to matter
+    let draws: Vec<_> = (0..16u8).map(|b| arbitration.select_jurors(&[b; 32], &parties).unwrap()).collect();
+    assert!(draws.iter().any(|draw| *draw != jury));
+}
+
+#[test]
+fn selection_needs_enough_eligible_jurors() {
+    let (arbitration, jurors) = arbitration_with(JURORS_PER_DISPUTE + 1);
+    assert!(arbitration.select_jurors(&[0; 32], &[jurors[0]]).is_ok());
+    assert_eq!(
+        arbitration.select_jurors(&[0; 32], &[jurors[0], jurors[1]]).unwrap_err(),
+        ViralSyncError::JurorPoolTooSmall.into()
+    );
+}
+
+#[test]
+fn commit_reveal_enforces_phases_and_commitments() {
+    let mut vote = seated_vote();
+    let juror = vote.jurors[0];
+    let salt = [9u8; 32];
+    let commitment = vote_commitment(true, &salt, &juror);
+    
+    assert_eq!(
+        vote.commit(&Pubkey::new_unique(), commitment, NOW).unwrap_err(),
+        ViralSyncError::NotSelectedJuror.into()
+    );
+    vote.commit(&juror, commitment, NOW).unwrap();
+    assert_eq!(vote.commit(&juror, commitment, NOW).unwrap_err(), ViralSyncError::VoteAlreadyCast.into());
+    
+    // Reveals only open once commits close
+    assert_eq!(vote.reveal(&juror, true, &salt, NOW).unwrap_err(), ViralSyncError::VotingPhaseClosed.into());
+    let reveal_at = vote.commit_deadline + 1;
+    assert_eq!(vote.reveal(&juror, false, &salt, reveal_at).unwrap_err(), ViralSyncError::InvalidVoteReveal.into());
+    vote.reveal(&juror, true, &salt, reveal_at).unwrap();
+    assert_eq!(vote.votes[0], Some(true));
+    
+    // Late commits and late reveals are refused
+    let late = vote.jurors[1];
+    assert_eq!(
+        vote.commit(&late, vote_commitment(true, &salt, &late), vote.commit_deadline + 1).unwrap_err(),
+        ViralSyncError::VotingPhaseClosed.into()
+    );
+    assert_eq!(
+        vote.reveal(&juror, true, &salt, vote.reveal_deadline + 1).unwrap_err(),
+        ViralSyncError::VotingPhaseClosed.into()
+    );
+}
+
+#[test]
+fn commitments_cannot_be_copied_between_jurors() {
+    let mut vote = seated_vote();
+    let (honest, copier) = (vote.jurors[0], vote.jurors[1]);
+    let salt = [3u8; 32];
+    let commitment = vote_commitment(true, &salt, &honest);
+    
+    vote.commit(&honest, commitment, NOW).unwrap();
+    vote.commit(&copier, commitment, NOW).unwrap();
+    let reveal_at = vote.commit_deadline + 1;
+    assert_eq!(vote.reveal(&copier, true, &salt, reveal_at).unwrap_err(), ViralSyncError::InvalidVoteReveal.into());
+}
+
+#[test]
+fn tally_needs_quorum_and_ties_dismiss() {
+    let mut vote = seated_vote();
+    cast(&mut vote, 0, true);
+    cast(&mut vote, 1, true);
+    assert!(!vote.tally().quorum_met());
+    assert!(!vote.can_tally(vote.reveal_deadline));
+    assert!(vote.can_tally(vote.reveal_deadline + 1));
+    
+    cast(&mut vote, 2, false);
+    cast(&mut vote, 3, false);
+    let tally = vote.tally();
+    assert!(tally.quorum_met());
+    assert!(!tally.upheld());
+    
+    cast(&mut vote, 4, true);
+    assert!(vote.tally().upheld());
+    // Everyone revealed: no need to wait for the deadline
+    assert!(vote.can_tally(vote.commit_deadline + 1));
+}
+
+#[test]
+fn no_shows_lose_a_slice_of_stake() {
+    let mut stake: JurorStake = zeroed(JurorStake::LEN);
+    stake.stake_lamports = MIN_JUROR_STAKE_LAMPORTS;
+    
+    let slashed = stake.slash_no_show();
+    assert_eq!(slashed, MIN_JUROR_STAKE_LAMPORTS * JUROR_NO_SHOW_SLASH_BPS / 10_000);
+    assert_eq!(stake.stake_lamports, MIN_JUROR_STAKE_LAMPORTS - slashed);
+    assert_eq!(stake.no_shows, 1);
+}
+
+// SlotHashes sysvar data for `slots`, newest first, each hashed to its own slot number
+fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+    let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+    for slot in slots {
+        data.extend_from_slice(&slot.to_le_bytes());
+        data.extend_from_slice(&[*slot as u8; 32]);
+    }
+    data
+}
+
+#[test]
+fn jury_is_drawn_from_the_committed_slot_only() {
+    let draw_slot = 100;
+    
+    // Not landed yet: nobody can see, let alone pick, the seed
+    assert_eq!(
+        draw_slot_hash(&slot_hashes(&[99, 98, 97]), draw_slot).unwrap_err(),
+        ViralSyncError::JuryDrawNotReady.into()
+    );
+    
+    // Later slots do not change the draw
+    let hash = draw_slot_hash(&slot_hashes(&[101, 100, 99]), draw_slot).unwrap();
+    assert_eq!(hash, Some([100; 32]));
+    assert_eq!(draw_slot_hash(&slot_hashes(&[140, 120, 101, 100, 99]), draw_slot).unwrap(), hash);
+    
+    // A skipped draw slot falls to the next slot produced
+    assert_eq!(draw_slot_hash(&slot_hashes(&[103, 102, 99]), draw_slot).unwrap(), Some([102; 32]));
+    
+    // Aged out of the sysvar: the draw must be committed again
+    assert_eq!(draw_slot_hash(&slot_hashes(&[700, 600, 101]), draw_slot).unwrap(), None);
+}
+
+#[test]
+fn the_referrer_never_sits_on_its_own_jury() {
+    let referrer = Pubkey::new_unique();
+    let (mut arbitration, _) = arbitration_with(JURORS_PER_DISPUTE);
+    arbitration.add_juror(referrer).unwrap();
+    let (arbitration_key, bump) = Pubkey::find_program_address(&[b"arbitration"], &viral_sync::ID);
+    arbitration.bump = bump;
+    
+    let referral_key = Pubkey::new_unique();
+    let mut referral: ReferralRecord = zeroed(ReferralRecord::LEN);
+    referral.referrer = referrer;
+    
+    let dispute_key = Pubkey::new_unique();
+    let mut dispute: DisputeRecord = zeroed(DisputeRecord::LEN);
+    dispute.referral = referral_key;
+    dispute.status = DisputeStatus::InArbitration;
+    
+    let (vote_key, vote_bump) = Pubkey::find_program_address(&[b"dispute_vote", dispute_key.as_ref()], &viral_sync::ID);
+    let mut vote: DisputeVote = zeroed(DisputeVote::LEN);
+    vote.bump = vote_bump;
+    vote.dispute = dispute_key;
+    vote.draw_slot = NOW_SLOT;
+    
+    let mut sysvar = Fixture::wallet(slot_hashes::ID, false);
+    sysvar.data = slot_hashes(&[NOW_SLOT, NOW_SLOT - 1]);
+    let mut fixtures = vec![
+        Fixture::program_account(arbitration_key, &arbitration),
+        Fixture::program_account(dispute_key, &dispute),
+        Fixture::program_account(referral_key, &referral),
+        Fixture::program_account(vote_key, &vote),
+        sysvar,
+    ];
+    run_handler!(DrawJurors, draw_jurors, fixtures).unwrap();
+    
+    let vote = DisputeVote::try_deserialize(&mut &fixtures[3].data[..]).unwrap();
+    assert!(vote.is_drawn());
+    assert!(!vote.jurors.contains(&referrer));
+}
@@ -0,0 +1,188 @@
+// Regression suite: an attacker signing every merchant-admin instruction against a victim's
+// merchant accounts must be rejected by account validation, while the real merchant passes.
+
+use std::collections::BTreeSet;
+use anchor_lang::prelude::*;
+use anchor_lang::Accounts;
+use viral_sync::instructions::*;
+use viral_sync::state::*;
+
+mod common;
+use common::*;
+
+// Runs account validation for `$accounts` once as the real merchant (must pass) and once with an
+// attacker signing against the same victim accounts (must fail)
+macro_rules! assert_only_merchant {
+    ($accounts:ty, $build:expr) => {{
+        let victim = merchant();
+        let attacker = Pubkey::new_unique();
+        let build = $build;
+        
+        let mut fixtures = build(&victim, victim.merchant);
+        let result = validate_accounts!($accounts, fixtures);
+        assert!(result.is_ok(), "real merchant was rejected: {:?}", result.err());
+        
+        let mut fixtures = build(&victim, attacker);
+        assert!(validate_accounts!($accounts, fixtures).is_err(), "attacker was accepted");
+    }};
+}
+
+macro_rules! validate_accounts {
+    ($accounts:ty, $fixtures:ident) => {{
+        let infos = infos(&mut $fixtures);
+        let mut remaining: &[AccountInfo] = &infos;
+        let mut bumps = Default::default();
+        <$accounts>::try_accounts(&viral_sync::ID, &mut remaining, &[], &mut bumps, &mut BTreeSet::new()).map(|_| ())
+    }};
+}
+
+#[test]
+fn only_merchant_can_issue_first_tokens() {
+    assert_only_merchant!(IssueFirstTokensAndLock, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::wallet(signer, true),
+        Fixture::mint(m.mint),
+    ]);
+}
+
+#[test]
+fn only_merchant_can_update_config() {
+    assert_only_merchant!(UpdateMerchantConfig, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::wallet(signer, true),
+    ]);
+}
+
+#[test]
+fn only_merchant_can_deposit_bond() {
+    assert_only_merchant!(DepositBond, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::program_account(m.vault_key, &m.vault),
+        Fixture::wallet(signer, true),
+        Fixture::system_program(),
+    ]);
+}
+
+#[test]
+fn only_merchant_can_request_bond_unlock() {
+    assert_only_merchant!(RequestBondUnlock, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::wallet(signer, true),
+    ]);
+}
+
+#[test]
+fn only_merchant_can_withdraw_bond() {
+    assert_only_merchant!(WithdrawBond, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::program_account(m.vault_key, &m.vault),
+        Fixture::wallet(signer, true),
+    ]);
+}
+
+#[test]
+fn only_merchant_can_initiate_close() {
+    assert_only_merchant!(InitiateCloseMerchant, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::wallet(signer, true),
+    ]);
+}
+
+#[test]
+fn only_merchant_can_cancel_close() {
+    assert_only_merchant!(CancelCloseMerchant, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::wallet(signer, true),
+    ]);
+    
+    assert_only_merchant!(CancelCloseMerchant, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::none(),
+        Fixture::wallet(signer, true),
+    ]);
+}
+
+#[test]
+fn only_merchant_can_finalize_close() {
+    assert_only_merchant!(FinalizeCloseMerchant, |m: &Merchant, signer: Pubkey| vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::wallet(signer, true),
+        Fixture::program_account(m.vault_key, &m.vault),
+        Fixture::mint(m.mint),
+        Fixture::program_account(m.treasury_key, &m.treasury),
+    ]);
+}
+
+#[test]
+fn only_merchant_can_respond_to_dispute() {
+    assert_only_merchant!(RespondToDispute, |m: &Merchant, signer: Pubkey| {
+        let dispute = DisputeRecord {
+            bump: 255,
+            merchant: m.merchant,
+            referral: Pubkey::new_unique(),
+            watchdog: Pubkey::new_unique(),
+            status: DisputeStatus::Pending,
+            stake_lamports: 0,
+            raised_at: 0,
+            resolved_at: None,
+            evidence_hash: None,
+            responded_at: None,
+            disputed_amount: 0,
+            escrow_bump: 255,
+        };
+        vec![
+            Fixture::program_account(Pubkey::new_unique(), &dispute),
+            Fixture::wallet(signer, true),
+        ]
+    });
+}
+
+#[test]
+fn attacker_cannot_swap_in_own_bond_against_victim_config() {
+    // Attacker owns a bond of their own but targets the victim's config to finalize its close
+    let victim = merchant();
+    let attacker = merchant();
+    let mut fixtures = vec![
+        Fixture::program_account(victim.config_key, &victim.config),
+        Fixture::program_account(attacker.bond_key, &attacker.bond),
+        Fixture::wallet(attacker.merchant, true),
+        Fixture::program_account(attacker.vault_key, &attacker.vault),
+        Fixture::mint(victim.mint),
+        Fixture::program_account(victim.treasury_key, &victim.treasury),
+    ];
+    assert!(validate_accounts!(FinalizeCloseMerchant, fixtures).is_err());
+}
+
+#[test]
+fn finalize_close_only_accepts_the_treasury_generation() {
+    // A holder's generation sits at a valid gen_v4 PDA but must not be closed as the treasury
+    let mut m = merchant();
+    m.treasury.is_treasury = false;
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(m.bond_key, &m.bond),
+        Fixture::wallet(m.merchant, true),
+        Fixture::program_account(m.vault_key, &m.vault),
+        Fixture::mint(m.mint),
+        Fixture::program_account(m.treasury_key, &m.treasury),
+    ];
+    assert!(validate_accounts!(FinalizeCloseMerchant, fixtures).is_err());
+}
+
+#[test]
+fn anyone_can_crank_an_expired_dispute() {
+    let mut fixtures = expired_dispute_fixtures(None, DisputeStatus::Pending, 0);
+    let result = validate_accounts!(ResolveExpiredDispute, fixtures);
+    assert!(result.is_ok(), "cranker was rejected: {:?}", result.err());
+}
+
+#[test]
+fn expired_dispute_only_settles_the_disputed_ledger() {
+    // Same merchant and mint, but another referrer's ledger
+    let mut fixtures = expired_dispute_fixtures(Some(Pubkey::new_unique()), DisputeStatus::Pending, 0);
+    assert!(validate_accounts!(ResolveExpiredDispute, fixtures).is_err());
+}
@@ -0,0 +1,159 @@
+// Walks a bond through deposit, unlock, withdrawal and share redemption, checking the balances.
+
+use anchor_lang::prelude::*;
+use viral_sync::errors::ViralSyncError;
+use viral_sync::instructions::bond_management::pay_from_vault;
+use viral_sync::instructions::*;
+use viral_sync::state::*;
+
+#[macro_use]
+mod common;
+use common::{merchant, Fixture};
+
+const VAULT_RENT: u64 = 1_000_000;
+
+fn new_bond() -> MerchantBond {
+    MerchantBond {
+        bump: 255,
+        merchant: Pubkey::new_unique(),
+        bonded_lamports: 0,
+        min_required_lamports: MIN_MERCHANT_BOND_LAMPORTS,
+        is_locked: true,
+        unlock_requested_at: 0,
+        vault_bump: 254,
+        redeemed_lamports: 0,
+        redeemed_tokens: 0,
+        mint: Pubkey::new_unique(),
+    }
+}
+
+#[test]
+fn bond_lifecycle_moves_lamports() {
+    let program_id = viral_sync::ID;
+    let vault_key = Pubkey::new_unique();
+    let merchant_key = Pubkey::new_unique();
+    let mut vault_lamports = VAULT_RENT;
+    let mut merchant_lamports = MIN_MERCHANT_BOND_LAMPORTS + 10_000_000;
+    let mut vault_data = [0u8; 0];
+    let mut merchant_data = [0u8; 0];
+    let vault = AccountInfo::new(&vault_key, false, true, &mut vault_lamports, &mut vault_data, &program_id, false, 0);
+    let merchant = AccountInfo::new(&merchant_key, true, true, &mut merchant_lamports, &mut merchant_data, &merchant_key, false, 0);
+    
+    let mut bond = new_bond();
+    let deposit = MIN_MERCHANT_BOND_LAMPORTS + 3_000_000;
+    
+    // deposit_bond: the system transfer lands in the vault and the ledger follows
+    **vault.try_borrow_mut_lamports().unwrap() += deposit;
+    **merchant.try_borrow_mut_lamports().unwrap() -= deposit;
+    bond.deposit(deposit).unwrap();
+    assert_eq!(bond.bonded_lamports, deposit);
+    assert_eq!(vault.lamports(), VAULT_RENT + deposit);
+    assert_eq!(merchant.lamports(), 7_000_000);
+    
+    // Locked bonds cannot be withdrawn
+    let now = 1_700_000_000;
+    assert_eq!(bond.withdraw(1_000_000, now).unwrap_err(), ViralSyncError::BondLocked.into());
+    
+    // Unlock starts the 48h timelock
+    bond.request_unlock(now);
+    assert_eq!(
+        bond.withdraw(1_000_000, now + BOND_UNLOCK_DELAY_SECS - 1).unwrap_err(),
+        ViralSyncError::BondTimelockActive.into()
+    );
+    
+    // The protocol floor stays put even after the timelock
+    let later = now + BOND_UNLOCK_DELAY_SECS;
+    assert_eq!(bond.withdraw(3_000_001, later).unwrap_err(), ViralSyncError::InsufficientBalance.into());
+    
+    bond.withdraw(3_000_000, later).unwrap();
+    pay_from_vault(&vault, &merchant, 3_000_000).unwrap();
+    assert_eq!(bond.bonded_lamports, MIN_MERCHANT_BOND_LAMPORTS);
+    assert_eq!(vault.lamports(), VAULT_RENT + MIN_MERCHANT_BOND_LAMPORTS);
+    assert_eq!(merchant.lamports(), 10_000_000);
+    
+    // Each withdrawal consumes the unlock
+    assert!(bond.is_locked);
+    assert_eq!(bond.withdraw(1, later + 1).unwrap_err(), ViralSyncError::BondLocked.into());
+}
+
+#[test]
+fn vault_cannot_pay_more_than_it_holds() {
+    let program_id = viral_sync::ID;
+    let vault_key = Pubkey::new_unique();
+    let holder_key = Pubkey::new_unique();
+    let mut vault_lamports = 100;
+    let mut holder_lamports = 0;
+    let mut vault_data = [0u8; 0];
+    let mut holder_data = [0u8; 0];
+    let vault = AccountInfo::new(&vault_key, false, true, &mut vault_lamports, &mut vault_data, &program_id, false, 0);
+    let holder = AccountInfo::new(&holder_key, true, true, &mut holder_lamports, &mut holder_data, &holder_key, false, 0);
+    
+    assert_eq!(pay_from_vault(&vault, &holder, 101).unwrap_err(), ViralSyncError::InsufficientBalance.into());
+    assert_eq!(vault.lamports(), 100);
+    assert_eq!(holder.lamports(), 0);
+}
+
+#[test]
+fn share_redemptions_pay_the_same_rate_in_any_order() {
+    let mut bond = new_bond();
+    bond.deposit(9_000_000).unwrap();
+    let mut supply = 900;
+    
+    // Three holders of 300 tokens each; burned tokens leave the supply
+    for _ in 0..3 {
+        let share = bond.pro_rata_share(300, supply);
+        assert_eq!(share, 3_000_000);
+        bond.record_share_redemption(share, 300).unwrap();
+        supply -= 300;
+    }
+    assert_eq!(bond.bonded_lamports, 0);
+    assert_eq!(bond.redeemed_lamports, 9_000_000);
+    assert_eq!(bond.redeemed_tokens, 900);
+    assert_eq!(bond.pro_rata_share(300, supply), 0);
+}
+
+#[test]
+fn treasury_held_supply_does_not_dilute_share_redemptions() {
+    let mut m = merchant();
+    m.config.is_active = false;
+    m.bond.bonded_lamports = 4_000_000;
+    
+    let holder = Pubkey::new_unique();
+    let (holder_gen_key, holder_gen_bump) = Pubkey::find_program_address(&[b"gen_v4", m.mint.as_ref(), holder.as_ref()], &viral_sync::ID);
+    let mut holder_gen: TokenGeneration = common::zeroed(TokenGeneration::LEN);
+    holder_gen.bump = holder_gen_bump;
+    holder_gen.gen1_balance = 100;
+    let (treasury_authority, _) = Pubkey::find_program_address(&[b"treasury", m.mint.as_ref()], &viral_sync::ID);
+    
+    // 1_000 minted: 600 never left the treasury, 100 with this holder, 300 with others
+    let mut mint = Fixture::mint(m.mint);
+    mint.data[36..44].copy_from_slice(&1_000u64.to_le_bytes());
+    let mut holder_ata = Fixture::token_account(Pubkey::new_unique(), m.mint, holder);
+    holder_ata.data[64..72].copy_from_slice(&100u64.to_le_bytes());
+    let mut treasury_ata = Fixture::token_account(Pubkey::new_unique(), m.mint, treasury_authority);
+    treasury_ata.data[64..72].copy_from_slice(&600u64.to_le_bytes());
+    let mut holder_wallet = Fixture::wallet(holder, true);
+    holder_wallet.lamports = 0;
+    let mut vault = Fixture::program_account(m.vault_key, &m.vault);
+    vault.lamports = VAULT_RENT + 4_000_000;
+    
+    let mut fixtures = vec![
+        Fixture::program_account(m.config_key, &m.config),
+        Fixture::program_account(holder_gen_key, &holder_gen),
+        holder_ata,
+        mint,
+        Fixture::program_account(m.bond_key, &m.bond),
+        holder_wallet,
+        vault,
+        Fixture::wallet(treasury_authority, false),
+        treasury_ata,
+        Fixture::token_program(),
+    ];
+    run_handler!(RedeemBondShare, redeem_bond_share, fixtures).unwrap();
+    
+    // A quarter of the tokens holders still have earns a quarter of the bond
+    assert_eq!(fixtures[5].lamports, 1_000_000);
+    assert_eq!(fixtures[6].lamports, VAULT_RENT + 3_000_000);
+    let bond = MerchantBond::try_deserialize(&mut &fixtures[4].data[..]).unwrap();
+    assert_eq!((bond.bonded_lamports, bond.redeemed_tokens), (3_000_000, 100));
+}
@@ -0,0 +1,85 @@
+// Oracle signer registry and the m-of-n gate on reputation updates.
+
+use std::collections::BTreeSet;
+use anchor_lang::prelude::*;
+use anchor_lang::Accounts;
+use viral_sync::errors::ViralSyncError;
+use viral_sync::instructions::*;
+use viral_sync::state::*;
+
+mod common;
+use common::*;
+
+fn authority_with(signer_count: usize) -> (OracleAuthority, Vec<Pubkey>) {
+    let (_, bump) = Pubkey::find_program_address(&[b"oracle_authority"], &viral_sync::ID);
+    let mut authority: OracleAuthority = zeroed(OracleAuthority::LEN);
+    authority.bump = bump;
+    authority.reputation_threshold = 1;
+    let signers: Vec<Pubkey> = (0..signer_count).map(|_| Pubkey::new_unique()).collect();
+    for signer in &signers {
+        authority.add_signer(*signer).unwrap();
+    }
+    (authority, signers)
+}
+
+fn authority_key() -> Pubkey {
+    Pubkey::find_program_address(&[b"oracle_authority"], &viral_sync::ID).0
+}
+
+fn validate_viral_oracle(authority: &OracleAuthority, crank: Pubkey) -> Result<()> {
+    let oracle: ViralOracle = zeroed(ViralOracle::LEN);
+    let mut fixtures = vec![
+        Fixture::program_account(Pubkey::new_unique(), &oracle),
+        Fixture::program_account(authority_key(), authority),
+        Fixture::wallet(crank, true),
+    ];
+    let infos = infos(&mut fixtures);
+    let mut remaining: &[AccountInfo] = &infos;
+    let mut bumps = Default::default();
+    ComputeViralOracle::try_accounts(&viral_sync::ID, &mut remaining, &[], &mut bumps, &mut BTreeSet::new()).map(|_| ())
+}
+
+#[test]
+fn only_registered_signers_update_the_viral_oracle() {
+    let (authority, signers) = authority_with(2);
+    assert!(validate_viral_oracle(&authority, signers[1]).is_ok());
+    
+    let err = validate_viral_oracle(&authority, Pubkey::new_unique()).unwrap_err();
+    assert_eq!(err, ViralSyncError::UnauthorizedOracle.into());
+}
+
+#[test]
+fn signer_registry_rejects_duplicates_and_overflow() {
+    let (mut authority, signers) = authority_with(MAX_ORACLE_SIGNERS);
+    assert_eq!(authority.add_signer(signers[0]).unwrap_err(), ViralSyncError::OracleSignerAlreadyRegistered.into());
+    assert_eq!(authority.add_signer(Pubkey::new_unique()).unwrap_err(), ViralSyncError::OracleRegistryFull.into());
+    
+    authority.remove_signer(&signers[0]).unwrap();
+    assert!(!authority.is_signer(&signers[0]));
+    assert_eq!(authority.signers().len(), MAX_ORACLE_SIGNERS - 1);
+    assert_eq!(authority.remove_signer(&signers[0]).unwrap_err(), ViralSyncError::UnauthorizedOracle.into());
+}
+
+#[test]
+fn threshold_stays_within_the_signer_count() {
+    let (mut authority, signers) = authority_with(3);
+    assert_eq!(authority.set_reputation_threshold(0).unwrap_err(), ViralSyncError::InvalidOracleThreshold.into());
+    assert_eq!(authority.set_reputation_threshold(4).unwrap_err(), ViralSyncError::InvalidOracleThreshold.into());
+    authority.set_reputation_threshold(3).unwrap();
+    
+    // Removing a signer would make the threshold unreachable
+    assert_eq!(authority.remove_signer(&signers[0]).unwrap_err(), ViralSyncError::InvalidOracleThreshold.into());
+    authority.set_reputation_threshold(2).unwrap();
+    authority.remove_signer(&signers[0]).unwrap();
+}
+
+#[test]
+fn approvals_count_distinct_registered_signers() {
+    let (authority, signers) = authority_with(3);
+    let outsider = Pubkey::new_unique();
+    
+    assert_eq!(authority.count_approvals([signers[0], signers[2]].iter()), 2);
+    // Repeating a signer or adding outsiders does not reach a higher threshold
+    assert_eq!(authority.count_approvals([signers[0], signers[0], outsider].iter()), 1);
+    assert_eq!(authority.count_approvals([outsider].iter()), 0);
+}
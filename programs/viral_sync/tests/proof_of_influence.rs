@@ -0,0 +1,100 @@
+// Proof of Influence is weighted by what was redeemed and only counts mints with a bonded merchant,
+// so dust redemptions at self-made merchants cannot farm a commission threshold.
+
+use std::collections::BTreeSet;
+use anchor_lang::prelude::*;
+use viral_sync::errors::ViralSyncError;
+use viral_sync::instructions::*;
+use viral_sync::state::*;
+
+mod common;
+use common::*;
+
+// Runs compute_poi_score on `fixtures` with `pairs` as the remaining accounts and returns the score
+fn compute(mut fixtures: Vec<Fixture>, mut pairs: Vec<Fixture>) -> Result<u32> {
+    install_clock();
+    let account_infos = infos(&mut fixtures);
+    let remaining_infos = infos(&mut pairs);
+    let mut remaining: &[AccountInfo] = &account_infos;
+    let mut bumps = Default::default();
+    let mut accounts = ComputePoiScore::try_accounts(&viral_sync::ID, &mut remaining, &[], &mut bumps, &mut BTreeSet::new())?;
+    compute_poi_score(Context::new(&viral_sync::ID, &mut accounts, &remaining_infos, bumps))?;
+    Ok(accounts.token_generation.poi_score)
+}
+
+fn generation(owner: Pubkey, mint: Pubkey, momentum: u64) -> TokenGeneration {
+    let mut gen: TokenGeneration = zeroed(TokenGeneration::LEN);
+    gen.owner = owner;
+    gen.mint = mint;
+    gen.poi_momentum = momentum;
+    gen.poi_momentum_at = NOW;
+    gen
+}
+
+fn bond(mint: Pubkey, bonded_lamports: u64) -> Fixture {
+    let mut bond: MerchantBond = zeroed(MerchantBond::LEN);
+    bond.mint = mint;
+    bond.bonded_lamports = bonded_lamports;
+    Fixture::program_account(Pubkey::new_unique(), &bond)
+}
+
+#[test]
+fn dust_redemptions_earn_proportionally_less_influence() {
+    let mut full: TokenGeneration = zeroed(TokenGeneration::LEN);
+    full.accrue_poi(NOW, 500, 500);
+    assert_eq!(full.poi_momentum, POI_POINTS_PER_REFERRAL);
+    
+    // Redeeming more than a referral's worth is capped at the full points
+    let mut over: TokenGeneration = zeroed(TokenGeneration::LEN);
+    over.accrue_poi(NOW, 5_000, 500);
+    assert_eq!(over.poi_momentum, POI_POINTS_PER_REFERRAL);
+    
+    let mut dust: TokenGeneration = zeroed(TokenGeneration::LEN);
+    dust.accrue_poi(NOW, 1, 500);
+    assert_eq!(dust.poi_momentum, 0);
+}
+
+#[test]
+fn only_bonded_mints_count_toward_the_score() {
+    let owner = Pubkey::new_unique();
+    let home_mint = Pubkey::new_unique();
+    let self_made_mint = Pubkey::new_unique();
+    let fixtures = || vec![
+        Fixture::program_account(Pubkey::new_unique(), &generation(owner, home_mint, 100)),
+        Fixture::wallet(owner, true),
+        bond(home_mint, MIN_MERCHANT_BOND_LAMPORTS),
+    ];
+    let pairs = |bonded_lamports: u64| vec![
+        Fixture::program_account(Pubkey::new_unique(), &generation(owner, self_made_mint, 1_000)),
+        bond(self_made_mint, bonded_lamports),
+    ];
+    
+    let score = compute(fixtures(), pairs(MIN_MERCHANT_BOND_LAMPORTS - 1)).unwrap();
+    assert_eq!(score, 100);
+    
+    let score = compute(fixtures(), pairs(MIN_MERCHANT_BOND_LAMPORTS)).unwrap();
+    assert_eq!(score, 1_100);
+    
+    // Without a bond on the target's own mint, its momentum does not count either
+    let mut unbonded = fixtures();
+    unbonded[2] = Fixture::none();
+    let score = compute(unbonded, pairs(MIN_MERCHANT_BOND_LAMPORTS)).unwrap();
+    assert_eq!(score, 1_000);
+}
+
+#[test]
+fn a_bond_must_belong_to_its_generations_mint() {
+    let owner = Pubkey::new_unique();
+    let home_mint = Pubkey::new_unique();
+    let fixtures = vec![
+        Fixture::program_account(Pubkey::new_unique(), &generation(owner, home_mint, 100)),
+        Fixture::wallet(owner, true),
+        bond(home_mint, MIN_MERCHANT_BOND_LAMPORTS),
+    ];
+    let pairs = vec![
+        Fixture::program_account(Pubkey::new_unique(), &generation(owner, Pubkey::new_unique(), 1_000)),
+        bond(home_mint, MIN_MERCHANT_BOND_LAMPORTS),
+    ];
+    let err = compute(fixtures, pairs).unwrap_err();
+    assert_eq!(err, ViralSyncError::InvalidPoiGeneration.into());
+}
@@ -0,0 +1,32 @@
+// Session allow-lists apply to the wallet that ends up with the tokens.
+
+use anchor_lang::prelude::*;
+use viral_sync::state::*;
+
+mod common;
+use common::zeroed;
+
+fn allow_listed(destinations: &[Pubkey]) -> SessionKey {
+    let mut session: SessionKey = zeroed(SessionKey::LEN);
+    session.allowed_destinations[..destinations.len()].copy_from_slice(destinations);
+    session.allowed_destination_count = destinations.len() as u8;
+    session
+}
+
+#[test]
+fn escrow_shares_are_checked_against_the_recipient() {
+    let friend = Pubkey::new_unique();
+    let session = allow_listed(&[friend]);
+    assert!(session.allows_escrow_recipient(Some(friend)));
+    assert!(!session.allows_escrow_recipient(Some(Pubkey::new_unique())));
+}
+
+#[test]
+fn allow_listed_sessions_cannot_fund_claim_code_escrows() {
+    assert!(!allow_listed(&[Pubkey::new_unique()]).allows_escrow_recipient(None));
+    
+    // Without an allow-list any escrow may be funded
+    let open = allow_listed(&[]);
+    assert!(open.allows_escrow_recipient(None));
+    assert!(open.allows_escrow_recipient(Some(Pubkey::new_unique())));
+}